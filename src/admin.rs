@@ -0,0 +1,347 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::{self, Next},
+    response::Response,
+    routing::{delete, get, post},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::api::{json_response, ApiError};
+use crate::config::Config;
+use crate::history::HistoryManager;
+use crate::pipeline::{self, RunOptions, RunOutcome};
+use crate::rss_server::{RssServer, RssServerConfig};
+use crate::run_log::{TaskRun, TaskRunStatus};
+use crate::state::GenerationGuard;
+
+/// 内存中保留的最近任务数，完整记录写入任务执行日志
+const MAX_RUNS: usize = 100;
+/// 管理 API 生成日报在任务执行日志中的任务名
+const ADMIN_JOB: &str = "admin";
+/// 管理 API 重新生成日报在任务执行日志中的任务名
+const ADMIN_REGENERATE_JOB: &str = "admin-regenerate";
+
+/// 任务类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunKind {
+    /// 获取新闻并生成新日报
+    Generate,
+    /// 重新生成已有日报的摘要
+    Regenerate,
+}
+
+/// 任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Running,
+    Succeeded,
+    /// 没有新内容，未生成日报
+    Skipped,
+    Failed,
+}
+
+impl From<TaskRunStatus> for RunStatus {
+    fn from(status: TaskRunStatus) -> Self {
+        match status {
+            TaskRunStatus::Succeeded => RunStatus::Succeeded,
+            TaskRunStatus::Skipped => RunStatus::Skipped,
+            TaskRunStatus::Failed => RunStatus::Failed,
+        }
+    }
+}
+
+/// 通过管理 API 触发的任务
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminRun {
+    pub id: String,
+    pub kind: RunKind,
+    /// 请求中的任务选项
+    pub options: Value,
+    /// 重新生成的日报 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub status: RunStatus,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// 生成或重新生成的日报 ID
+    pub report_id: Option<String>,
+    pub message: Option<String>,
+}
+
+/// 管理 API 的任务记录
+#[derive(Debug, Default)]
+pub struct AdminState {
+    runs: Mutex<VecDeque<AdminRun>>,
+}
+
+impl AdminState {
+    fn start_run(&self, kind: RunKind, options: Value, target: Option<String>) -> AdminRun {
+        let run = AdminRun {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            options,
+            target,
+            status: RunStatus::Running,
+            started_at: Utc::now(),
+            finished_at: None,
+            report_id: None,
+            message: None,
+        };
+        
+        let mut runs = self.runs.lock().unwrap();
+        runs.push_front(run.clone());
+        runs.truncate(MAX_RUNS);
+        run
+    }
+    
+    fn finish_run(&self, id: &str, status: RunStatus, report_id: Option<String>, message: String) {
+        let mut runs = self.runs.lock().unwrap();
+        if let Some(run) = runs.iter_mut().find(|run| run.id == id) {
+            run.status = status;
+            run.finished_at = Some(Utc::now());
+            run.report_id = report_id;
+            run.message = Some(message);
+        }
+    }
+    
+    /// 最近的任务，从新到旧
+    fn runs(&self) -> Vec<AdminRun> {
+        self.runs.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+/// 获取生成锁，与定时任务、其他管理任务和其他进程互斥
+fn try_lock(server: &RssServer) -> Result<GenerationGuard, ApiError> {
+    server
+        .shared()
+        .try_lock_generation()
+        .map_err(|e| ApiError::internal(format!("获取生成锁失败: {}", e)))?
+        .ok_or_else(|| ApiError::conflict("已有任务正在运行，请稍后再试"))
+}
+
+/// `POST /admin/reports/{id}/regenerate` 请求体
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegenerateOptions {
+    /// 是否使用批量模式，未指定时沿用原日报的处理模式
+    #[serde(default)]
+    batch: Option<bool>,
+}
+
+/// 管理 API 路由，所有请求都需要 `Authorization: Bearer <RSS_ADMIN_TOKEN>`
+pub fn router(server: RssServer) -> Router<RssServer> {
+    Router::new()
+        .route("/admin/run", post(trigger_run_handler))
+        .route("/admin/runs", get(list_runs_handler))
+        .route("/admin/runs/:id", get(run_detail_handler))
+        .route("/admin/reports/:id", delete(delete_report_handler))
+        .route("/admin/reports/:id/regenerate", post(regenerate_report_handler))
+        .route_layer(middleware::from_fn_with_state(server, require_token))
+}
+
+/// 校验管理令牌
+async fn require_token(
+    State(server): State<RssServer>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let config = server.config();
+    let Some(expected) = config.admin_token.as_deref() else {
+        return Err(ApiError::not_found("管理 API 未启用"));
+    };
+    
+    if token_matches(request.headers(), expected) {
+        Ok(next.run(request).await)
+    } else {
+        Err(ApiError::unauthorized("缺少或无效的管理令牌"))
+    }
+}
+
+/// 请求是否携带了有效的管理令牌，未启用管理 API 时始终为 false
+pub fn is_authorized(config: &RssServerConfig, headers: &HeaderMap) -> bool {
+    config
+        .admin_token
+        .as_deref()
+        .is_some_and(|expected| token_matches(headers, expected))
+}
+
+fn token_matches(headers: &HeaderMap, expected: &str) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), expected.as_bytes()))
+}
+
+/// 比较令牌时不因提前返回泄露匹配长度
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// 解析 JSON 请求体，空请求体使用默认值
+fn parse_body<T: Default + for<'de> Deserialize<'de>>(body: &Bytes) -> Result<T, ApiError> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(T::default());
+    }
+    serde_json::from_slice(body).map_err(|e| ApiError::bad_request(format!("无效的请求体: {}", e)))
+}
+
+/// `POST /admin/run`：在后台触发一次日报生成
+async fn trigger_run_handler(
+    State(server): State<RssServer>,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let options: RunOptions = parse_body(&body)?;
+    if options.days == 0 {
+        return Err(ApiError::bad_request("days 必须大于 0"));
+    }
+    
+    let guard = try_lock(&server)?;
+    let run = server.admin().start_run(RunKind::Generate, json!(options), None);
+    info!("🛠️ 管理 API 触发日报生成: {} ({:?})", run.id, options);
+    
+    let mut task_run = TaskRun::start(ADMIN_JOB);
+    task_run.id = run.id.clone();
+    tokio::spawn(async move {
+        let result = async {
+            let config = Config::load()?;
+            let mut history_manager = HistoryManager::new()?;
+            pipeline::run(&config, &options, server.store(), &mut history_manager).await
+        }
+        .await;
+        
+        task_run.apply_outcome(&result);
+        let message = match &result {
+            Ok(RunOutcome::Generated { report, fetched, .. }) => {
+                server.shared().notify_reports_changed();
+                format!("获取 {} 条新闻，生成 {} 条摘要", fetched, report.items.len())
+            }
+            Ok(RunOutcome::NothingNew { fetched }) => format!("获取到的 {} 条新闻都已处理过", fetched),
+            Ok(RunOutcome::NoNews) | Err(_) => {
+                let error = task_run.error.clone().unwrap_or_default();
+                warn!("管理 API 触发的日报生成失败: {}", error);
+                error
+            }
+        };
+        server.admin().finish_run(&task_run.id, task_run.status.into(), task_run.report_id.clone(), message);
+        task_run.save();
+        drop(guard);
+    });
+    
+    Ok(json_response(StatusCode::ACCEPTED, &json!({ "status": "accepted", "run": run })))
+}
+
+/// `GET /admin/runs`：最近的任务及其状态
+async fn list_runs_handler(State(server): State<RssServer>) -> Response {
+    let runs = server.admin().runs();
+    json_response(StatusCode::OK, &json!({
+        "status": "success",
+        "running": runs.iter().any(|run| run.status == RunStatus::Running),
+        "runs": runs,
+    }))
+}
+
+/// `GET /admin/runs/{id}`：单个任务的状态
+async fn run_detail_handler(
+    State(server): State<RssServer>,
+    Path(id): Path<String>,
+) -> Result<Response, ApiError> {
+    let run = server
+        .admin()
+        .runs()
+        .into_iter()
+        .find(|run| run.id == id)
+        .ok_or_else(|| ApiError::not_found(format!("未找到任务: {}", id)))?;
+    Ok(json_response(StatusCode::OK, &json!({ "status": "success", "run": run })))
+}
+
+/// `DELETE /admin/reports/{id}`：删除日报并移除历史记录中的引用
+async fn delete_report_handler(
+    State(server): State<RssServer>,
+    Path(id): Path<String>,
+) -> Result<Response, ApiError> {
+    let _guard = try_lock(&server)?;
+    
+    let deleted = server.store().delete(&id).map_err(|e| {
+        warn!("删除日报失败: {}", e);
+        ApiError::internal(format!("删除日报失败: {}", e))
+    })?;
+    if !deleted {
+        return Err(ApiError::not_found(format!("未找到日报: {}", id)));
+    }
+    server.shared().notify_reports_changed();
+    
+    if let Err(e) = HistoryManager::new().and_then(|mut history_manager| history_manager.remove_report(&id)) {
+        warn!("更新历史记录失败: {}", e);
+    }
+    info!("🗑️ 管理 API 删除了日报: {}", id);
+    
+    Ok(json_response(StatusCode::OK, &json!({ "status": "success", "deleted": id })))
+}
+
+/// `POST /admin/reports/{id}/regenerate`：在后台重新生成日报摘要
+async fn regenerate_report_handler(
+    State(server): State<RssServer>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let options: RegenerateOptions = parse_body(&body)?;
+    
+    let exists = server.store().report_ids().map_err(|e| {
+        warn!("加载报告失败: {}", e);
+        ApiError::internal(format!("加载报告失败: {}", e))
+    })?.contains(&id);
+    if !exists {
+        return Err(ApiError::not_found(format!("未找到日报: {}", id)));
+    }
+    
+    let guard = try_lock(&server)?;
+    let run = server.admin().start_run(RunKind::Regenerate, json!(options), Some(id.clone()));
+    info!("🛠️ 管理 API 触发重新生成日报 {}: {}", id, run.id);
+    
+    let mut task_run = TaskRun::start(ADMIN_REGENERATE_JOB);
+    task_run.id = run.id.clone();
+    tokio::spawn(async move {
+        let result = match Config::load() {
+            Ok(config) => pipeline::regenerate(&config, server.store(), &id, options.batch).await,
+            Err(e) => Err(e),
+        };
+        let message = match result {
+            Ok(Some(report)) => {
+                server.shared().notify_reports_changed();
+                task_run.status = TaskRunStatus::Succeeded;
+                task_run.summarized = report.items.len();
+                task_run.report_id = Some(report.id.clone());
+                format!("重新生成了 {} 条新闻的摘要", report.items.len())
+            }
+            Ok(None) => {
+                let message = format!("未找到日报: {}", id);
+                task_run.error = Some(message.clone());
+                message
+            }
+            Err(e) => {
+                warn!("重新生成日报 {} 失败: {}", id, e);
+                task_run.error = Some(e.to_string());
+                e.to_string()
+            }
+        };
+        server.admin().finish_run(&task_run.id, task_run.status.into(), task_run.report_id.clone(), message);
+        task_run.save();
+        drop(guard);
+    });
+    
+    Ok(json_response(StatusCode::ACCEPTED, &json!({ "status": "accepted", "run": run })))
+}
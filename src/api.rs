@@ -0,0 +1,352 @@
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tracing::warn;
+
+use crate::rss_server::RssServer;
+use crate::{DailyReport, NewsItem};
+
+/// 每页默认条数
+const DEFAULT_LIMIT: usize = 10;
+/// 每页最大条数，超出时返回 400 而不是静默截断
+const MAX_LIMIT: usize = 100;
+
+/// `fields=` 可选的字段
+const REPORT_FIELDS: &[&str] = &[
+    "id", "edition", "date", "title", "url", "summary", "item_count", "sources", "items",
+    "notices",
+];
+/// 未指定 `fields=` 时返回的字段
+const DEFAULT_FIELDS: &[&str] = &["id", "edition", "date", "title", "url", "summary", "items"];
+
+/// JSON 错误响应，格式为 `{"status": "error", "code": ..., "message": ...}`
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            code: "bad_request",
+            message: message.into(),
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            code: "not_found",
+            message: message.into(),
+        }
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::UNAUTHORIZED,
+            code: "unauthorized",
+            message: message.into(),
+        }
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::CONFLICT,
+            code: "conflict",
+            message: message.into(),
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            code: "internal_error",
+            message: message.into(),
+        }
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::bad_request(rejection.body_text())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = json!({
+            "status": "error",
+            "code": self.code,
+            "message": self.message,
+        });
+        json_response(self.status, &body)
+    }
+}
+
+pub fn json_response(status: StatusCode, body: &Value) -> Response {
+    (
+        status,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        serde_json::to_string_pretty(body).unwrap(),
+    )
+        .into_response()
+}
+
+/// `/api/reports` 查询参数
+#[derive(Debug, Default, Deserialize)]
+pub struct ReportsQuery {
+    /// 每页条数，默认 10，最大 100
+    limit: Option<usize>,
+    /// 页码，从 1 开始；与 `cursor` 同时提供时以 `cursor` 为准
+    page: Option<usize>,
+    /// 上一页返回的 `next_cursor`
+    cursor: Option<String>,
+    /// 起始日期（含），`YYYY-MM-DD` 或 RFC 3339
+    since: Option<String>,
+    /// 截止日期（含），`YYYY-MM-DD` 或 RFC 3339
+    until: Option<String>,
+    /// 只保留指定来源的新闻
+    source: Option<String>,
+    /// 全文搜索新闻标题、描述、链接和摘要
+    q: Option<String>,
+    /// 逗号分隔的返回字段
+    fields: Option<String>,
+}
+
+/// 解析后的过滤条件
+struct ReportFilter {
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    source: Option<String>,
+    q: Option<String>,
+}
+
+impl ReportFilter {
+    fn from_query(query: &ReportsQuery) -> Result<Self, ApiError> {
+        Ok(Self {
+            since: query.since.as_deref().map(|value| parse_date(value, false)).transpose()?,
+            until: query.until.as_deref().map(|value| parse_date(value, true)).transpose()?,
+            source: query.source.clone().filter(|source| !source.is_empty()),
+            q: query.q.as_ref()
+                .map(|q| q.trim().to_lowercase())
+                .filter(|q| !q.is_empty()),
+        })
+    }
+
+    fn filters_items(&self) -> bool {
+        self.source.is_some() || self.q.is_some()
+    }
+
+    fn matches_item(&self, item: &NewsItem) -> bool {
+        if let Some(source) = &self.source {
+            if !item.source.eq_ignore_ascii_case(source) {
+                return false;
+            }
+        }
+        if let Some(q) = &self.q {
+            let haystacks = [
+                item.title.as_str(),
+                item.description.as_str(),
+                item.link.as_str(),
+                item.ai_summary.as_deref().unwrap_or_default(),
+            ];
+            if !haystacks.iter().any(|text| text.to_lowercase().contains(q)) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// 应用过滤条件；按新闻过滤时只保留匹配的新闻，没有匹配新闻的日报被排除
+    fn apply(&self, report: &DailyReport) -> Option<DailyReport> {
+        if self.since.is_some_and(|since| report.date < since) {
+            return None;
+        }
+        if self.until.is_some_and(|until| report.date > until) {
+            return None;
+        }
+        if !self.filters_items() {
+            return Some(report.clone());
+        }
+
+        let items: Vec<NewsItem> = report.items
+            .iter()
+            .filter(|item| self.matches_item(item))
+            .cloned()
+            .collect();
+        if items.is_empty() {
+            return None;
+        }
+
+        Some(DailyReport {
+            items,
+            ..report.clone()
+        })
+    }
+}
+
+/// 解析日期参数，`end_of_day` 为 true 时 `YYYY-MM-DD` 取当天结束时刻
+fn parse_date(value: &str, end_of_day: bool) -> Result<DateTime<Utc>, ApiError> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| ApiError::bad_request(format!("无效的日期: {} (格式应为 YYYY-MM-DD 或 RFC 3339)", value)))?;
+    let time = if end_of_day {
+        date.and_hms_milli_opt(23, 59, 59, 999)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    Ok(time.unwrap().and_utc())
+}
+
+/// 解析 `fields=` 参数
+fn parse_fields(fields: Option<&str>) -> Result<Vec<&str>, ApiError> {
+    let Some(fields) = fields else {
+        return Ok(DEFAULT_FIELDS.to_vec());
+    };
+
+    let mut selected = Vec::new();
+    for field in fields.split(',').map(str::trim).filter(|field| !field.is_empty()) {
+        let Some(known) = REPORT_FIELDS.iter().find(|known| **known == field) else {
+            return Err(ApiError::bad_request(format!(
+                "未知字段: {} (可选: {})",
+                field,
+                REPORT_FIELDS.join(", ")
+            )));
+        };
+        if !selected.contains(known) {
+            selected.push(*known);
+        }
+    }
+
+    if selected.is_empty() {
+        return Err(ApiError::bad_request("fields 不能为空"));
+    }
+    Ok(selected)
+}
+
+/// 按字段投影日报
+fn project_report(server: &RssServer, report: &DailyReport, fields: &[&str]) -> Value {
+    let mut object = Map::new();
+    for field in fields {
+        let value = match *field {
+            "id" => json!(report.id),
+            "edition" => json!(report.edition),
+            "date" => json!(report.date),
+            "title" => json!(report.title()),
+            "url" => json!(server.report_link(report)),
+            "summary" => json!(report.summary),
+            "item_count" => json!(report.items.len()),
+            "sources" => {
+                let mut sources: Vec<&str> = report.items.iter().map(|item| item.source.as_str()).collect();
+                sources.sort_unstable();
+                sources.dedup();
+                json!(sources)
+            }
+            "items" => json!(report.items),
+            "notices" => json!(report.notices),
+            _ => continue,
+        };
+        object.insert(field.to_string(), value);
+    }
+    Value::Object(object)
+}
+
+/// `GET /api/reports`：分页、过滤的日报列表
+pub async fn list_reports_handler(
+    State(server): State<RssServer>,
+    query: Result<Query<ReportsQuery>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let Query(query) = query.map_err(ApiError::from)?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    if limit == 0 || limit > MAX_LIMIT {
+        return Err(ApiError::bad_request(format!("limit 必须在 1 到 {} 之间", MAX_LIMIT)));
+    }
+    if query.page == Some(0) {
+        return Err(ApiError::bad_request("page 从 1 开始"));
+    }
+    let filter = ReportFilter::from_query(&query)?;
+    let fields = parse_fields(query.fields.as_deref())?;
+
+    let reports = server.all_reports().map_err(|e| {
+        warn!("加载报告失败: {}", e);
+        ApiError::internal(format!("加载报告失败: {}", e))
+    })?;
+
+    let matched: Vec<DailyReport> = reports
+        .iter()
+        .filter_map(|report| filter.apply(report))
+        .collect();
+    let total = matched.len();
+
+    // 游标为上一页最后一期的 ID，日报按从新到旧排列
+    let start = match (&query.cursor, query.page) {
+        (Some(cursor), _) => match matched.iter().position(|report| &report.id == cursor) {
+            Some(index) => index + 1,
+            None => return Err(ApiError::bad_request(format!("无效的游标: {}", cursor))),
+        },
+        (None, Some(page)) => (page - 1).saturating_mul(limit),
+        (None, None) => 0,
+    };
+
+    let page_reports: Vec<&DailyReport> = matched.iter().skip(start).take(limit).collect();
+    let has_more = start + page_reports.len() < total;
+    let next_cursor = if has_more {
+        page_reports.last().map(|report| report.id.clone())
+    } else {
+        None
+    };
+
+    let body = json!({
+        "status": "success",
+        "count": page_reports.len(),
+        "total": total,
+        "limit": limit,
+        "page": start / limit + 1,
+        "has_more": has_more,
+        "next_cursor": next_cursor,
+        "reports": page_reports
+            .iter()
+            .map(|report| project_report(&server, report, &fields))
+            .collect::<Vec<_>>(),
+    });
+
+    Ok(json_response(StatusCode::OK, &body))
+}
+
+/// `GET /api/reports/{id}`：单期日报详情，也接受日期（返回当天最新一期）
+pub async fn report_detail_handler(
+    State(server): State<RssServer>,
+    Path(id): Path<String>,
+    query: Result<Query<ReportsQuery>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let Query(query) = query.map_err(ApiError::from)?;
+    let fields = parse_fields(query.fields.as_deref())?;
+
+    let (report, nav) = server
+        .find_report(&id)
+        .map_err(|e| {
+            warn!("加载报告失败: {}", e);
+            ApiError::internal(format!("加载报告失败: {}", e))
+        })?
+        .ok_or_else(|| ApiError::not_found(format!("未找到日报: {}", id)))?;
+
+    let body = json!({
+        "status": "success",
+        "report": project_report(&server, &report, &fields),
+        "prev": nav.prev,
+        "next": nav.next,
+    });
+
+    Ok(json_response(StatusCode::OK, &body))
+}
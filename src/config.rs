@@ -1,190 +1,820 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// 统一配置文件，相对于工作目录，可用 `--config` 或 `RUST_DAILY_CONFIG` 指定其他路径
+pub const CONFIG_FILE: &str = "rust-daily.toml";
+/// 早期版本只包含 RSS 源和定时任务的配置文件，找不到 `rust-daily.toml` 时仍会读取
+pub const LEGACY_CONFIG_FILE: &str = "rss_sources.toml";
+
+/// 命令行参数指定的配置文件和覆盖项，启动时由 `init` 设置
+static OPTIONS: OnceLock<LoadOptions> = OnceLock::new();
+
+#[derive(Debug, Clone, Default)]
+struct LoadOptions {
+    path: Option<PathBuf>,
+    overrides: ConfigOverrides,
+}
+
+/// 命令行参数对配置的覆盖，优先级高于配置文件和环境变量
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    pub days: Option<u64>,
+    pub batch: Option<bool>,
+    pub auto_publish: Option<bool>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub unix_socket: Option<PathBuf>,
+}
+
+/// 设置配置文件路径和命令行覆盖项，之后每次 `Config::load` 都会使用
+///
+/// 只应在启动时调用一次，重复调用会被忽略。
+pub fn init(path: Option<PathBuf>, overrides: ConfigOverrides) {
+    let _ = OPTIONS.set(LoadOptions { path, overrides });
+}
+
+fn options() -> LoadOptions {
+    OPTIONS.get().cloned().unwrap_or_default()
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RssSource {
     pub name: String,
     pub url: String,
+    /// 附加到该源所有新闻上的标签，用于 `/feed/tag/{tag}`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
+/// 所有配置，按 默认值 < 配置文件 < 环境变量 < 命令行参数 的顺序合并
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
-    pub rss_sources: Vec<RssSource>,
+    /// RSS 源，配置文件中未配置时使用内置的默认源
+    #[serde(default = "default_sources")]
+    pub sources: Vec<RssSource>,
+    #[serde(default)]
+    pub fetch: FetchConfig,
+    #[serde(default)]
+    pub llm: LlmConfig,
+    #[serde(default)]
+    pub generation: GenerationConfig,
+    #[serde(default)]
+    pub history: RetentionPolicy,
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
+    /// 读取的配置文件，没有配置文件时为 `None`
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// 早期 `rss_sources.toml` 的格式
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LegacyConfig {
+    rss_sources: Vec<RssSource>,
+    #[serde(default)]
+    retention: RetentionPolicy,
+    #[serde(default)]
+    source_health: FetchConfig,
+    #[serde(default)]
+    schedules: Vec<ScheduleConfig>,
+}
+
+impl From<LegacyConfig> for Config {
+    fn from(legacy: LegacyConfig) -> Self {
+        Self {
+            sources: legacy.rss_sources,
+            fetch: legacy.source_health,
+            history: legacy.retention,
+            scheduler: SchedulerConfig { jobs: legacy.schedules, ..SchedulerConfig::default() },
+            ..Self::default()
+        }
+    }
+}
+
+/// RSS 获取设置，包括源健康策略：连续失败的源会被暂停获取，定期重试成功后自动恢复
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FetchConfig {
+    /// 命令行生成日报时获取最近几天的新闻
+    #[serde(default = "default_fetch_days")]
+    pub days: u64,
+    /// 连续失败多少次后暂停获取，0 表示从不暂停
+    #[serde(default = "default_quarantine_after")]
+    pub quarantine_after: u32,
+    /// 暂停期间每隔多少小时重试一次
+    #[serde(default = "default_retry_after_hours")]
+    pub retry_after_hours: u64,
+    /// 超过多少天没有新内容视为陈旧，0 表示不检查
+    #[serde(default = "default_stale_days")]
+    pub stale_days: u64,
+}
+
+fn default_fetch_days() -> u64 {
+    1
+}
+
+fn default_quarantine_after() -> u32 {
+    5
+}
+
+fn default_retry_after_hours() -> u64 {
+    24
+}
+
+fn default_stale_days() -> u64 {
+    30
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            days: default_fetch_days(),
+            quarantine_after: default_quarantine_after(),
+            retry_after_hours: default_retry_after_hours(),
+            stale_days: default_stale_days(),
+        }
+    }
+}
+
+/// DeepSeek 接口设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LlmConfig {
+    /// API 密钥，建议通过 `DEEPSEEK_API_KEY` 环境变量设置而不是写在配置文件中
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default = "default_llm_model")]
+    pub model: String,
+    #[serde(default = "default_llm_base_url")]
+    pub base_url: String,
+}
+
+fn default_llm_model() -> String {
+    "deepseek-chat".to_string()
+}
+
+fn default_llm_base_url() -> String {
+    "https://api.deepseek.com".to_string()
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            api_key: None,
+            model: default_llm_model(),
+            base_url: default_llm_base_url(),
+        }
+    }
+}
+
+/// 日报生成设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenerationConfig {
+    /// 命令行生成日报时使用批量处理模式（默认逐条生成摘要）
+    #[serde(default)]
+    pub batch: bool,
+    /// 日报保存目录
+    #[serde(default = "default_reports_dir")]
+    pub reports_dir: String,
+    /// 生成后显示发布说明
+    #[serde(default)]
+    pub auto_publish: bool,
+}
+
+fn default_reports_dir() -> String {
+    "./reports".to_string()
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        Self {
+            batch: false,
+            reports_dir: default_reports_dir(),
+            auto_publish: false,
+        }
+    }
+}
+
+/// RSS 服务器设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerConfig {
+    #[serde(default = "default_server_host")]
+    pub host: String,
+    #[serde(default = "default_server_port")]
+    pub port: u16,
+    /// 设置后监听 Unix domain socket 而不是 TCP 端口（仅 Unix 平台）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unix_socket: Option<PathBuf>,
+    /// feed 中链接使用的访问地址，未设置时根据监听地址推导
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(default = "default_server_title")]
+    pub title: String,
+    #[serde(default = "default_server_description")]
+    pub description: String,
+    /// 管理 API 的访问令牌，未设置时不启用 `/admin` 路由
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_token: Option<String>,
+}
+
+fn default_server_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_server_port() -> u16 {
+    7080
+}
+
+fn default_server_title() -> String {
+    "Rust Daily 技术日报".to_string()
+}
+
+fn default_server_description() -> String {
+    "每日精选 Rust 技术新闻和资讯".to_string()
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: default_server_host(),
+            port: default_server_port(),
+            unix_socket: None,
+            base_url: None,
+            title: default_server_title(),
+            description: default_server_description(),
+            admin_token: None,
+        }
+    }
+}
+
+/// 守护进程设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchedulerConfig {
+    /// 定时任务，未配置时使用默认任务（UTC 每 4 小时一次）
+    #[serde(default)]
+    pub jobs: Vec<ScheduleConfig>,
+    /// 单独运行 `--daemon` 时提供 `/metrics` 的监听地址；组合模式下由 RSS 服务器提供
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_listen: Option<SocketAddr>,
+}
+
+/// 一个命名的定时任务
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduleConfig {
+    pub name: String,
+    /// 带秒的 cron 表达式，例如 `0 0 8 * * *`
+    pub cron: String,
+    /// IANA 时区名称，例如 `Asia/Shanghai`
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    /// 获取最近几天的新闻
+    #[serde(default = "default_schedule_days")]
+    pub days: u64,
+    /// 使用批量处理模式（默认逐条生成摘要）
+    #[serde(default)]
+    pub batch: bool,
+    /// 忽略历史记录，例如周报需要重新收录本周已出现过的新闻
+    #[serde(default)]
+    pub force: bool,
+    /// 额外把日报以 Markdown 格式写入该目录
+    #[serde(default)]
+    pub markdown_dir: Option<String>,
+    /// 没有执行记录时（首次启动）立即执行一次
+    #[serde(default)]
+    pub run_on_start: bool,
+    /// 启动时补执行停机期间错过的任务
+    #[serde(default = "default_catch_up")]
+    pub catch_up: bool,
+    /// 补执行时获取新闻的最长天数，停机再久也不超过该值
+    #[serde(default = "default_catch_up_max_days")]
+    pub catch_up_max_days: u64,
+    /// 触发时上一次生成仍在运行的处理方式
+    #[serde(default)]
+    pub overlap: OverlapPolicy,
+}
+
+/// 定时任务触发时已有生成任务在运行的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapPolicy {
+    /// 跳过本次执行
+    #[default]
+    Skip,
+    /// 等待正在运行的任务结束后执行，最多排队一次
+    Queue,
+    /// 取消正在运行的定时任务后执行
+    Cancel,
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn default_schedule_days() -> u64 {
+    1
+}
+
+fn default_catch_up() -> bool {
+    true
+}
+
+fn default_catch_up_max_days() -> u64 {
+    7
+}
+
+impl ScheduleConfig {
+    /// 未配置定时任务时使用的默认任务，与早期版本的行为一致
+    pub fn default_job() -> Self {
+        Self {
+            name: "default".to_string(),
+            cron: "0 0 */4 * * *".to_string(),
+            timezone: default_timezone(),
+            days: default_schedule_days(),
+            batch: false,
+            force: false,
+            markdown_dir: None,
+            run_on_start: true,
+            catch_up: default_catch_up(),
+            catch_up_max_days: default_catch_up_max_days(),
+            overlap: OverlapPolicy::default(),
+        }
+    }
+}
+
+/// 历史记录保留策略，守护进程启动时和每次定时任务后自动执行（每天最多一次）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetentionPolicy {
+    /// 记录最长保留天数，0 表示不按时间清理
+    #[serde(default = "default_max_age_days")]
+    pub max_age_days: u64,
+    /// 最多保留的记录条数，超出时删除最早的记录，0 表示不限制
+    #[serde(default = "default_max_records")]
+    pub max_records: usize,
+}
+
+fn default_max_age_days() -> u64 {
+    90
+}
+
+fn default_max_records() -> usize {
+    10000
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_age_days: default_max_age_days(),
+            max_records: default_max_records(),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// 访问地址，未设置 `base_url` 时根据监听地址推导
+    pub fn effective_base_url(&self) -> String {
+        if let Some(base_url) = &self.base_url {
+            return base_url.clone();
+        }
+        match self.host.parse::<std::net::IpAddr>() {
+            Ok(std::net::IpAddr::V6(ip)) => format!("http://[{}]:{}", ip, self.port),
+            _ => format!("http://{}:{}", self.host, self.port),
+        }
+    }
+}
+
+/// RSS 源的 URL 必须是带主机名的 http 或 https 地址
+pub fn validate_source_url(url: &str) -> Result<()> {
+    let url = url.trim();
+    if url.is_empty() {
+        bail!("URL 不能为空");
+    }
+    let parsed = reqwest::Url::parse(url).map_err(|e| anyhow!("URL {} 无效: {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        bail!("URL {} 必须以 http:// 或 https:// 开头", url);
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        bail!("URL {} 缺少主机名", url);
+    }
+    Ok(())
+}
+
+/// 默认的 Rust 相关 RSS 源
+fn default_sources() -> Vec<RssSource> {
+    vec![
+        RssSource {
+            name: "Rust Blog".to_string(),
+            url: "https://blog.rust-lang.org/feed.xml".to_string(),
+            tags: vec!["official".to_string(), "announcement".to_string()],
+        },
+        RssSource {
+            name: "This Week in Rust".to_string(),
+            url: "https://this-week-in-rust.org/rss.xml".to_string(),
+            tags: Vec::new(),
+        },
+        RssSource {
+            name: "Rust Users Forum".to_string(),
+            url: "https://users.rust-lang.org/latest.rss".to_string(),
+            tags: Vec::new(),
+        },
+        RssSource {
+            name: "Rust Internals".to_string(),
+            url: "https://internals.rust-lang.org/latest.rss".to_string(),
+            tags: Vec::new(),
+        },
+        RssSource {
+            name: "Jorge Aparicio's Blog".to_string(),
+            url: "https://blog.japaric.io/index.xml".to_string(),
+            tags: Vec::new(),
+        },
+    ]
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            sources: default_sources(),
+            fetch: FetchConfig::default(),
+            llm: LlmConfig::default(),
+            generation: GenerationConfig::default(),
+            history: RetentionPolicy::default(),
+            server: ServerConfig::default(),
+            scheduler: SchedulerConfig::default(),
+            path: None,
+        }
+    }
 }
 
 impl Config {
+    /// 生效的定时任务列表
+    pub fn effective_schedules(&self) -> Vec<ScheduleConfig> {
+        if self.scheduler.jobs.is_empty() {
+            vec![ScheduleConfig::default_job()]
+        } else {
+            self.scheduler.jobs.clone()
+        }
+    }
+
+    /// 加载配置，找不到配置文件时使用默认值并创建示例文件
+    ///
+    /// 配置文件存在但格式错误或配置无效时返回错误，不会回退到默认配置。
     pub fn load() -> Result<Self> {
-        // 尝试从外部配置文件加载
-        if let Ok(config) = Self::load_from_file("rss_sources.toml") {
-            return Ok(config);
+        let config = Self::resolve()?;
+        if config.path.is_none() {
+            if let Err(e) = Self::create_example_file() {
+                eprintln!("警告: 无法创建示例配置文件: {}", e);
+            }
         }
-        
-        // 如果文件不存在，使用默认配置并创建示例文件
-        let config = Self::default_config();
-        if let Err(e) = Self::create_example_file() {
-            eprintln!("警告: 无法创建示例配置文件: {}", e);
+        Ok(config)
+    }
+    
+    /// 与 `load` 相同，但找不到配置文件时不创建示例文件
+    pub fn resolve() -> Result<Self> {
+        let config = match Self::find_file()? {
+            Some(path) => Self::load_from_file(&path)?,
+            None => Self::default(),
+        };
+        config.with_env_and_overrides()
+    }
+    
+    /// 重新读取配置，配置文件不存在或无效时返回错误，调用方应继续使用原配置
+    pub fn reload() -> Result<Self> {
+        let path = Self::find_file()?
+            .ok_or_else(|| anyhow!("找不到配置文件 {}", CONFIG_FILE))?;
+        Self::load_from_file(&path)?.with_env_and_overrides()
+    }
+    
+    /// 要读取的配置文件：`--config`、`RUST_DAILY_CONFIG`、`rust-daily.toml`、`rss_sources.toml`
+    ///
+    /// 显式指定的文件不存在时返回错误，都没有找到时返回 `None`。
+    pub fn find_file() -> Result<Option<PathBuf>> {
+        let explicit = options()
+            .path
+            .or_else(|| std::env::var_os("RUST_DAILY_CONFIG").filter(|path| !path.is_empty()).map(PathBuf::from));
+        if let Some(path) = explicit {
+            if !path.exists() {
+                bail!("配置文件不存在: {}", path.display());
+            }
+            return Ok(Some(path));
         }
         
+        Ok([CONFIG_FILE, LEGACY_CONFIG_FILE]
+            .into_iter()
+            .map(PathBuf::from)
+            .find(|path| path.exists()))
+    }
+    
+    fn load_from_file(path: &Path) -> Result<Self> {
+        let config = Self::parse_file(path)?;
+        let problems = config.validate();
+        if !problems.is_empty() {
+            bail!(
+                "{} 配置无效:\n{}",
+                path.display(),
+                problems.iter().map(|problem| format!("  - {}", problem)).collect::<Vec<_>>().join("\n")
+            );
+        }
         Ok(config)
     }
     
-    fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
-        let config: Config = toml::from_str(&content)?;
+    /// 只解析配置文件，不检查内容，错误信息中包含出错的行号和列号
+    ///
+    /// 同时支持 `rust-daily.toml` 和早期 `rss_sources.toml` 的格式。
+    pub fn parse_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("无法读取 {}", path.display()))?;
+        let mut config = Self::parse_str(&content).map_err(|e| anyhow!("{} 格式错误: {}", path.display(), e))?;
+        config.path = Some(path.to_path_buf());
         Ok(config)
     }
     
-    fn default_config() -> Self {
-        // 默认的 Rust 相关 RSS 源
-        let default_sources = vec![
-            RssSource {
-                name: "Rust Blog".to_string(),
-                url: "https://blog.rust-lang.org/feed.xml".to_string(),
-            },
-            RssSource {
-                name: "This Week in Rust".to_string(),
-                url: "https://this-week-in-rust.org/rss.xml".to_string(),
-            },
-            RssSource {
-                name: "Rust Users Forum".to_string(),
-                url: "https://users.rust-lang.org/latest.rss".to_string(),
-            },
-            RssSource {
-                name: "Rust Internals".to_string(),
-                url: "https://internals.rust-lang.org/latest.rss".to_string(),
-            },
-            RssSource {
-                name: "Jorge Aparicio's Blog".to_string(),
-                url: "https://blog.japaric.io/index.xml".to_string(),
-            },
-        ];
-
-        Config {
-            rss_sources: default_sources,
+    fn parse_str(content: &str) -> Result<Self, toml::de::Error> {
+        let table: toml::Table = toml::from_str(content)?;
+        if table.contains_key("rss_sources") {
+            return toml::from_str::<LegacyConfig>(content).map(Self::from);
         }
+        toml::from_str(content)
+    }
+    
+    /// 配置文件是否为早期 `rss_sources.toml` 的格式
+    pub fn is_legacy(&self) -> Result<bool> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        let table: toml::Table = toml::from_str(&std::fs::read_to_string(path)?)?;
+        Ok(table.contains_key("rss_sources"))
+    }
+    
+    fn with_env_and_overrides(mut self) -> Result<Self> {
+        self.apply_env()?;
+        self.apply_overrides(&options().overrides);
+        Ok(self)
+    }
+    
+    /// 用环境变量覆盖配置，`.env` 文件中的变量同样生效
+    pub fn apply_env(&mut self) -> Result<()> {
+        let var = |name: &str| std::env::var(name).ok();
+        let non_empty = |name: &str| var(name).filter(|value| !value.is_empty());
+        
+        if let Some(api_key) = non_empty("DEEPSEEK_API_KEY") {
+            self.llm.api_key = Some(api_key);
+        }
+        if let Some(reports_dir) = non_empty("REPORTS_DIR") {
+            self.generation.reports_dir = reports_dir;
+        }
+        if let Some(auto_publish) = var("AUTO_PUBLISH") {
+            self.generation.auto_publish = auto_publish == "true";
+        }
+        if let Some(host) = non_empty("RSS_HOST") {
+            self.server.host = host;
+        }
+        if let Some(port) = non_empty("RSS_PORT") {
+            self.server.port = port
+                .parse()
+                .map_err(|_| anyhow!("环境变量 RSS_PORT 无效: {}", port))?;
+        }
+        if let Some(socket_path) = non_empty("RSS_UNIX_SOCKET") {
+            self.server.unix_socket = Some(PathBuf::from(socket_path));
+        }
+        if let Some(base_url) = non_empty("RSS_BASE_URL") {
+            self.server.base_url = Some(base_url);
+        }
+        if let Some(title) = var("RSS_TITLE") {
+            self.server.title = title;
+        }
+        if let Some(description) = var("RSS_DESCRIPTION") {
+            self.server.description = description;
+        }
+        if let Some(token) = non_empty("RSS_ADMIN_TOKEN") {
+            self.server.admin_token = Some(token);
+        }
+        Ok(())
+    }
+    
+    fn apply_overrides(&mut self, overrides: &ConfigOverrides) {
+        if let Some(days) = overrides.days {
+            self.fetch.days = days;
+        }
+        if let Some(batch) = overrides.batch {
+            self.generation.batch = batch;
+        }
+        if let Some(auto_publish) = overrides.auto_publish {
+            self.generation.auto_publish = auto_publish;
+        }
+        if let Some(host) = &overrides.host {
+            self.server.host = host.clone();
+        }
+        if let Some(port) = overrides.port {
+            self.server.port = port;
+        }
+        if let Some(socket_path) = &overrides.unix_socket {
+            self.server.unix_socket = Some(socket_path.clone());
+        }
+    }
+    
+    /// 检查 RSS 源配置：名称不能为空，URL 必须是 http(s) 地址，名称和 URL 都不能重复
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut names = HashSet::new();
+        let mut urls = HashSet::new();
+        
+        for (i, source) in self.sources.iter().enumerate() {
+            let name = source.name.trim();
+            let label = if name.is_empty() {
+                format!("第 {} 个 RSS 源", i + 1)
+            } else {
+                format!("第 {} 个 RSS 源「{}」", i + 1, name)
+            };
+            
+            if name.is_empty() {
+                problems.push(format!("{}: 名称不能为空", label));
+            } else if !names.insert(name) {
+                problems.push(format!("{}: 名称重复", label));
+            }
+            
+            if let Err(e) = validate_source_url(&source.url) {
+                problems.push(format!("{}: {}", label, e));
+            } else if !urls.insert(source.url.trim()) {
+                problems.push(format!("{}: URL {} 重复", label, source.url.trim()));
+            }
+        }
+        
+        problems
+    }
+    
+    /// 以 TOML 格式显示生效的配置，密钥和令牌会被隐藏，`--print-config` 使用
+    pub fn to_display_toml(&self) -> Result<String> {
+        const MASK: &str = "********";
+        let mut config = self.clone();
+        if config.llm.api_key.is_some() {
+            config.llm.api_key = Some(MASK.to_string());
+        }
+        if config.server.admin_token.is_some() {
+            config.server.admin_token = Some(MASK.to_string());
+        }
+        config.server.base_url = Some(config.server.effective_base_url());
+        config.scheduler.jobs = config.effective_schedules();
+        Ok(toml::to_string_pretty(&config)?)
     }
     
     fn create_example_file() -> Result<()> {
-        if Path::new("rss_sources.toml").exists() {
+        if Path::new(CONFIG_FILE).exists() {
             return Ok(()); // 文件已存在，不覆盖
         }
         
-        let example_content = r#"# Rust Daily RSS 源配置文件
-# 
-# 格式: name = "RSS源名称", url = "RSS链接地址"
-# 支持的源类型：RSS 2.0, Atom 1.0
-# 
-# 配置说明：
-# - name: RSS 源的显示名称，会在日报中显示
-# - url: RSS feed 的完整 URL 地址
-# - 新增源后重新运行程序即可生效
-# - 建议添加与 Rust 技术相关的高质量源
-
-[[rss_sources]]
+        let example_content = r#"# Rust Daily 配置文件
+#
+# 所有设置都可以省略，省略时使用默认值。
+# 优先级：默认值 < 本文件 < 环境变量（含 .env）< 命令行参数
+# 用 `rust-daily --print-config` 查看合并后生效的配置，`--check-config` 检查本文件。
+
+# RSS 源，支持 RSS 2.0
+# - name: RSS 源的显示名称，会在日报中显示，不能重复
+# - url: RSS feed 的完整 http(s) 地址，不能重复
+# - tags: 可选，附加到该源所有新闻上的标签，例如 tags = ["official"]
+
+[[sources]]
 name = "Rust Blog"
 url = "https://blog.rust-lang.org/feed.xml"
+tags = ["official", "announcement"]
 
-[[rss_sources]]
+[[sources]]
 name = "This Week in Rust"
 url = "https://this-week-in-rust.org/rss.xml"
 
-[[rss_sources]]
+[[sources]]
 name = "Rust Users Forum"
 url = "https://users.rust-lang.org/latest.rss"
 
-[[rss_sources]]
+[[sources]]
 name = "Rust Internals"
 url = "https://internals.rust-lang.org/latest.rss"
 
-[[rss_sources]]
+[[sources]]
 name = "Jorge Aparicio's Blog"
 url = "https://blog.japaric.io/index.xml"
 
-# 添加更多 RSS 源示例（已注释，取消注释即可启用）:
-
-# [[rss_sources]]
+# 更多 RSS 源示例（取消注释即可启用）:
+# [[sources]]
 # name = "Rust中文社区"
 # url = "https://rustcc.cn/rss"
 
-# [[rss_sources]]
-# name = "Steve Klabnik's Blog"
-# url = "https://words.steveklabnik.com/feed.xml"
+[fetch]
+days = 1                    # 获取最近几天的新闻（--days）
+quarantine_after = 5        # 连续失败多少次后暂停获取该源，0 表示从不暂停
+retry_after_hours = 24      # 暂停期间每隔多少小时重试一次，成功后自动恢复
+stale_days = 30             # 超过多少天没有新内容视为陈旧（--source-report），0 表示不检查
 
-# [[rss_sources]]
-# name = "Without Boats"
-# url = "https://without.boats/index.xml"
+[llm]
+# api_key = "..."           # 建议用 DEEPSEEK_API_KEY 环境变量设置
+model = "deepseek-chat"
+base_url = "https://api.deepseek.com"
 
-# [[rss_sources]]
-# name = "Amos's Blog"
-# url = "https://fasterthanli.me/index.xml"
+[generation]
+batch = false               # 批量处理模式（--batch-mode）
+reports_dir = "./reports"   # 日报保存目录（REPORTS_DIR）
+auto_publish = false        # 生成后显示发布说明（AUTO_PUBLISH、--publish）
 
-# [[rss_sources]]
-# name = "Baby Steps"
-# url = "http://smallcultfollowing.com/babysteps/blog/feeds/all.atom.xml"
+[history]                   # 历史记录保留策略，每天最多自动执行一次
+max_age_days = 90           # 记录最长保留天数，0 表示不按时间清理
+max_records = 10000         # 最多保留的记录条数，0 表示不限制
 
-# [[rss_sources]]
-# name = "Rust GameDev"
-# url = "https://gamedev.rs/news/index.xml"
+[server]
+host = "127.0.0.1"          # 监听地址（RSS_HOST、--host）
+port = 7080                 # 端口（RSS_PORT、--port）
+# unix_socket = "/run/rust-daily.sock"      # 改为监听 Unix socket（RSS_UNIX_SOCKET、--unix-socket）
+# base_url = "https://daily.example.com"    # feed 中链接使用的地址，默认根据监听地址推导（RSS_BASE_URL）
+title = "Rust Daily 技术日报"                 # RSS_TITLE
+description = "每日精选 Rust 技术新闻和资讯"   # RSS_DESCRIPTION
+# admin_token = "..."       # 启用管理 API，建议用 RSS_ADMIN_TOKEN 环境变量设置
 
-# [[rss_sources]]
-# name = "Rust Magazine"
-# url = "https://rustmagazine.github.io/rust_magazine_2021/feed.xml"
+# 单独运行 --daemon 时提供 Prometheus 指标（组合模式下使用 RSS 服务器的 /metrics）:
+# [scheduler]
+# metrics_listen = "127.0.0.1:9464"
 
-# 注意事项：
-# 1. 添加新源时请确保 URL 有效
-# 2. 建议测试新源是否返回有效的 RSS/Atom 内容
-# 3. 过多的源可能影响生成速度
-# 4. 某些源可能有访问限制或需要特殊处理
+# 守护进程定时任务（可配置多个；未配置时 UTC 每 4 小时运行一次）:
+# [[scheduler.jobs]]
+# name = "morning"              # 任务名称，不能重复
+# cron = "0 0 8 * * *"          # 带秒的 cron 表达式：秒 分 时 日 月 周
+# timezone = "Asia/Shanghai"    # IANA 时区，默认 UTC
+# days = 1                      # 获取最近几天的新闻
+# batch = false                 # 是否使用批量处理模式
+# run_on_start = true           # 首次启动（没有执行记录）时立即执行一次
+# catch_up = true               # 启动时补执行停机期间错过的任务
+# catch_up_max_days = 7         # 补执行时最多获取最近几天的新闻
+# overlap = "skip"              # 上一次仍在运行时：skip 跳过 / queue 排队一次 / cancel 取消上一次
+#
+# [[scheduler.jobs]]
+# name = "weekly"
+# cron = "0 0 9 * * Mon"
+# timezone = "Asia/Shanghai"
+# days = 7
+# batch = true
+# force = true                  # 周报不受历史记录去重影响
+# markdown_dir = "./weekly"     # 额外输出 Markdown 文件
 "#;
         
-        std::fs::write("rss_sources.toml", example_content)?;
-        println!("✅ 已创建示例配置文件: rss_sources.toml");
-        println!("💡 您可以编辑此文件来添加或修改 RSS 源");
+        std::fs::write(CONFIG_FILE, example_content)?;
+        println!("✅ 已创建示例配置文件: {}", CONFIG_FILE);
+        println!("💡 您可以编辑此文件来添加或修改 RSS 源和其他设置");
         
         Ok(())
     }
     
-    /// 添加新的 RSS 源到配置文件
-    pub fn add_source_to_file(name: &str, url: &str) -> Result<()> {
-        let config_path = Path::new("rss_sources.toml");
+    /// 添加新的 RSS 源到配置文件，追加到文件末尾以保留原有内容和注释
+    pub fn add_source_to_file(name: &str, url: &str) -> Result<PathBuf> {
+        validate_source_url(url)?;
         
-        // 如果文件不存在，先创建
-        if !config_path.exists() {
-            Self::create_example_file()?;
-        }
+        // 如果没有配置文件，先创建
+        let path = match Self::find_file()? {
+            Some(path) => path,
+            None => {
+                Self::create_example_file()?;
+                PathBuf::from(CONFIG_FILE)
+            }
+        };
         
-        // 读取现有配置
-        let mut config = Self::load_from_file("rss_sources.toml")
-            .unwrap_or_else(|_| Self::default_config());
+        // 读取现有配置，格式错误时不修改文件
+        let config = Self::load_from_file(&path)?;
         
         // 检查是否已存在相同的源
-        for source in &config.rss_sources {
+        for source in &config.sources {
             if source.name == name {
-                return Err(anyhow::anyhow!("RSS 源 '{}' 已存在", name));
+                return Err(anyhow!("RSS 源 '{}' 已存在", name));
             }
             if source.url == url {
-                return Err(anyhow::anyhow!("URL '{}' 已存在", url));
+                return Err(anyhow!("URL '{}' 已存在", url));
             }
         }
         
-        // 添加新源
-        config.rss_sources.push(RssSource {
-            name: name.to_string(),
-            url: url.to_string(),
-        });
-        
-        // 序列化并写入文件
-        let toml_content = toml::to_string_pretty(&config)?;
-        
-        // 添加注释头部
-        let content_with_header = format!(
-            "# Rust Daily RSS 源配置文件\n# \n# 格式: name = \"RSS源名称\", url = \"RSS链接地址\"\n# 支持的源类型：RSS 2.0, Atom 1.0\n# \n# 配置说明：\n# - name: RSS 源的显示名称，会在日报中显示\n# - url: RSS feed 的完整 URL 地址\n# - 新增源后重新运行程序即可生效\n# - 建议添加与 Rust 技术相关的高质量源\n\n{}",
-            toml_content
-        );
+        let key = if config.is_legacy()? { "rss_sources" } else { "sources" };
+        let mut content = std::fs::read_to_string(&path)?;
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&format!(
+            "\n[[{}]]\nname = {}\nurl = {}\n",
+            key,
+            toml::Value::String(name.to_string()),
+            toml::Value::String(url.to_string())
+        ));
+        Self::parse_str(&content).map_err(|e| anyhow!("添加后的配置无法解析: {}", e))?;
         
-        std::fs::write("rss_sources.toml", content_with_header)?;
+        std::fs::write(&path, content)?;
         
-        Ok(())
+        Ok(path)
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,12 @@
 use anyhow::Result;
-use chrono::Utc;
+use rig::client::CompletionClient;
 use rig::completion::Prompt;
 use rig::providers::deepseek;
+use std::time::Instant;
 use tracing::{debug, info};
 
+use crate::config::LlmConfig;
+use crate::telemetry;
 use crate::{DailyReport, NewsItem};
 
 #[derive(Debug, Clone)]
@@ -21,6 +24,61 @@ impl DailyGenerator {
         Self { agent }
     }
 
+    /// 按 `[llm]` 配置创建 DeepSeek 客户端和编辑角色的 agent
+    pub fn from_config(config: &LlmConfig) -> Result<Self> {
+        let api_key = Self::api_key(config)?;
+        let client = deepseek::Client::builder(api_key)
+            .base_url(&config.base_url)
+            .build()
+            .map_err(|e| anyhow::anyhow!("创建 DeepSeek 客户端失败: {}", e))?;
+        let agent = client
+            .agent(&config.model)
+            .preamble("你是 Rust 中文社区的专业技术编辑，负责整理每日 Rust 技术资讯。你需要按照 rustcc.cn 日报的格式和风格，用专业但易懂的中文编写技术日报。重点关注技术细节、实用价值和社区动态，保持客观中性的技术写作风格。")
+            .build();
+        Ok(Self::new(agent))
+    }
+
+    /// 用不消耗 token 的模型列表接口检查 DeepSeek API 是否可用，返回响应耗时
+    pub async fn probe_api(config: &LlmConfig) -> Result<std::time::Duration> {
+        let api_key = Self::api_key(config)?;
+        
+        let started = std::time::Instant::now();
+        let response = reqwest::Client::new()
+            .get(format!("{}/models", config.base_url.trim_end_matches('/')))
+            .bearer_auth(api_key)
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await?;
+        
+        match response.status() {
+            status if status.is_success() => Ok(started.elapsed()),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                Err(anyhow::anyhow!("API 密钥无效 (HTTP {})", response.status()))
+            }
+            status => Err(anyhow::anyhow!("HTTP 错误: {}", status)),
+        }
+    }
+
+    fn api_key(config: &LlmConfig) -> Result<&str> {
+        config
+            .api_key
+            .as_deref()
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("未设置 DeepSeek API 密钥（DEEPSEEK_API_KEY 环境变量或 [llm] api_key）"))
+    }
+
+    /// 发送一次请求，并记录耗时、结果和 token 用量指标
+    async fn prompt(&self, kind: &'static str, prompt: &str) -> Result<String> {
+        let started = Instant::now();
+        let result = self.agent.prompt(prompt).extended_details().await;
+        let tokens = result
+            .as_ref()
+            .ok()
+            .map(|response| (response.total_usage.input_tokens, response.total_usage.output_tokens));
+        telemetry::record_llm_request(kind, started.elapsed(), tokens);
+        Ok(result?.output)
+    }
+
     pub async fn generate_daily_report(&self, items: Vec<NewsItem>, use_single_processing: bool) -> Result<DailyReport> {
         if use_single_processing {
             self.generate_daily_report_single_mode(items).await
@@ -81,11 +139,16 @@ impl DailyGenerator {
         // 生成整体日报摘要
         let overall_summary = self.generate_overall_summary(&processed_items).await?;
 
-        Ok(DailyReport {
-            date: Utc::now(),
-            items: limited_items,
-            summary: self.format_daily_content(&processed_items, &overall_summary),
-        })
+        let summary = self.format_daily_content(&processed_items, &overall_summary);
+        let items = processed_items
+            .into_iter()
+            .map(|processed| NewsItem {
+                ai_summary: Some(processed.ai_summary),
+                ..processed.original_item
+            })
+            .collect();
+
+        Ok(DailyReport::new(items, summary))
     }
 
     async fn generate_single_item_summary(&self, item: &NewsItem) -> Result<String> {
@@ -110,8 +173,7 @@ impl DailyGenerator {
 
         debug!("发送单条新闻摘要请求到 DeepSeek");
         
-        let summary = self.agent.prompt(&prompt).await?;
-        Ok(summary.to_string())
+        self.prompt("item_summary", &prompt).await
     }
 
     async fn generate_overall_summary(&self, processed_items: &[ProcessedNewsItem]) -> Result<String> {
@@ -134,8 +196,7 @@ impl DailyGenerator {
             ));
         }
 
-        let summary = self.agent.prompt(&prompt).await?;
-        Ok(summary.to_string())
+        self.prompt("overall_summary", &prompt).await
     }
 
     fn format_daily_content(&self, processed_items: &[ProcessedNewsItem], overall_summary: &str) -> String {
@@ -273,11 +334,7 @@ Reddit：[讨论链接]
         // 批量生成摘要
         let batch_summary = self.generate_batch_summary(&limited_items).await?;
 
-        Ok(DailyReport {
-            date: Utc::now(),
-            items: limited_items,
-            summary: batch_summary,
-        })
+        Ok(DailyReport::new(limited_items, batch_summary))
     }
 
     async fn generate_batch_summary(&self, items: &[NewsItem]) -> Result<String> {
@@ -299,15 +356,14 @@ Reddit：[讨论链接]
             if let Some(date) = item.pub_date {
                 prompt.push_str(&format!("   发布时间: {}\n", date.format("%Y-%m-%d")));
             }
-            prompt.push_str("\n");
+            prompt.push('\n');
         }
         
         prompt.push_str("\n请按照模板格式生成今日 Rust 技术日报的整体摘要。");
 
         debug!("发送批量摘要请求到 DeepSeek");
         
-        let summary = self.agent.prompt(&prompt).await?;
-        Ok(summary.to_string())
+        self.prompt("batch_summary", &prompt).await
     }
 
     fn get_batch_template(&self) -> String {
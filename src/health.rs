@@ -0,0 +1,342 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::config::Config;
+use crate::history::HistoryManager;
+use crate::report_store::ReportStore;
+use crate::run_log::{RunLog, TaskRunStatus};
+use crate::scheduler::parse_schedules;
+use crate::source_status::SourceStatusStore;
+use crate::DailyGenerator;
+
+/// 可用空间低于该值时警告
+const DISK_WARN_BYTES: u64 = 1024 * 1024 * 1024;
+/// 可用空间低于该值时判定失败
+const DISK_FAIL_BYTES: u64 = 100 * 1024 * 1024;
+/// 判断错过执行时给进行中的任务留出的时间
+const SCHEDULE_GRACE_MINUTES: i64 = 30;
+/// LLM 探测结果的缓存时间，避免频繁请求 `/health?verbose=1` 时反复访问 API
+const LLM_PROBE_TTL_SECS: u64 = 300;
+
+static LLM_PROBE_CACHE: Mutex<Option<(Instant, CheckResult)>> = Mutex::new(None);
+
+/// 检查结果，按严重程度排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    /// 详细说明，可能包含文件路径和错误信息，匿名请求时省略
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub message: String,
+}
+
+impl CheckResult {
+    fn new(name: impl Into<String>, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// 一次完整的健康检查
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// 所有检查项中最严重的结果
+    pub status: CheckStatus,
+    pub checked_at: DateTime<Utc>,
+    pub checks: Vec<CheckResult>,
+}
+
+impl HealthReport {
+    pub fn is_healthy(&self) -> bool {
+        self.status != CheckStatus::Fail
+    }
+
+    /// 去掉各检查项的详细说明，只保留名称和结果
+    pub fn without_details(mut self) -> Self {
+        for check in &mut self.checks {
+            check.message.clear();
+        }
+        self
+    }
+
+    /// 未通过的检查项
+    pub fn problems(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks.iter().filter(|check| check.status != CheckStatus::Ok)
+    }
+
+    pub fn display(&self) {
+        println!("🩺 健康检查:");
+        for check in &self.checks {
+            let icon = match check.status {
+                CheckStatus::Ok => "✅",
+                CheckStatus::Warn => "⚠️",
+                CheckStatus::Fail => "❌",
+            };
+            println!("  {} {}: {}", icon, check.name, check.message);
+        }
+
+        let failed = self.checks.iter().filter(|check| check.status == CheckStatus::Fail).count();
+        let warned = self.checks.iter().filter(|check| check.status == CheckStatus::Warn).count();
+        println!();
+        if failed > 0 {
+            println!("❌ {} 项失败，{} 项警告", failed, warned);
+        } else if warned > 0 {
+            println!("⚠️ 全部通过，{} 项警告", warned);
+        } else {
+            println!("✅ 全部通过");
+        }
+    }
+}
+
+/// 健康检查的范围
+#[derive(Debug, Clone, Copy)]
+pub struct CheckOptions {
+    /// 请求 LLM 接口确认可用，会产生一次外部 API 调用
+    pub probe_llm: bool,
+    /// 检查定时任务是否按计划执行，只在运行调度器的进程中有意义
+    pub schedules: bool,
+}
+
+/// 执行健康检查
+pub async fn run_checks(config: &Config, store: &ReportStore, options: CheckOptions) -> HealthReport {
+    let mut checks = vec![
+        check_reports_dir(store.dir()),
+        check_disk_space(store.dir()),
+        check_history(store),
+    ];
+    if options.schedules {
+        checks.extend(check_schedules(config));
+    }
+    if options.probe_llm {
+        checks.push(check_llm(config).await);
+    }
+    checks.extend(check_sources(config));
+
+    HealthReport {
+        status: checks.iter().map(|check| check.status).max().unwrap_or(CheckStatus::Ok),
+        checked_at: Utc::now(),
+        checks,
+    }
+}
+
+/// 报告目录存在且可写
+fn check_reports_dir(dir: &Path) -> CheckResult {
+    const NAME: &str = "reports_dir";
+    if !dir.is_dir() {
+        return CheckResult::new(NAME, CheckStatus::Fail, format!("报告目录不存在: {}", dir.display()));
+    }
+
+    let probe = dir.join(format!(".health-check-{}", std::process::id()));
+    match std::fs::write(&probe, b"ok").and_then(|_| std::fs::remove_file(&probe)) {
+        Ok(()) => CheckResult::new(NAME, CheckStatus::Ok, format!("{} 可写", dir.display())),
+        Err(e) => CheckResult::new(NAME, CheckStatus::Fail, format!("{} 不可写: {}", dir.display(), e)),
+    }
+}
+
+/// 报告目录所在磁盘的可用空间
+fn check_disk_space(dir: &Path) -> CheckResult {
+    const NAME: &str = "disk_space";
+    let Some(available) = available_space(dir) else {
+        return CheckResult::new(NAME, CheckStatus::Warn, "无法获取可用空间");
+    };
+
+    let message = format!("可用空间 {:.1} GiB", available as f64 / 1024.0 / 1024.0 / 1024.0);
+    let status = if available < DISK_FAIL_BYTES {
+        CheckStatus::Fail
+    } else if available < DISK_WARN_BYTES {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Ok
+    };
+    CheckResult::new(NAME, status, message)
+}
+
+#[cfg(unix)]
+fn available_space(dir: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(dir.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: path 是以 NUL 结尾的有效字符串，stat 指向已初始化的结构体
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn available_space(_dir: &Path) -> Option<u64> {
+    None
+}
+
+/// 历史记录文件可以解析，引用的日报存在
+fn check_history(store: &ReportStore) -> CheckResult {
+    const NAME: &str = "history";
+    match HistoryManager::check_integrity(store) {
+        Ok(integrity) => {
+            let mut problems = Vec::new();
+            if integrity.pending_runs > 0 {
+                problems.push(format!("{} 个待恢复的任务", integrity.pending_runs));
+            }
+            if integrity.missing_reports > 0 {
+                problems.push(format!("{} 份引用的日报已不存在", integrity.missing_reports));
+            }
+
+            if problems.is_empty() {
+                CheckResult::new(NAME, CheckStatus::Ok, format!("{} 条记录", integrity.items))
+            } else {
+                CheckResult::new(
+                    NAME,
+                    CheckStatus::Warn,
+                    format!("{} 条记录，{}", integrity.items, problems.join("，")),
+                )
+            }
+        }
+        Err(e) => CheckResult::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+/// 每个定时任务的上次成功执行时间是否与计划相符
+fn check_schedules(config: &Config) -> Vec<CheckResult> {
+    let schedules = match parse_schedules(config) {
+        Ok(schedules) => schedules,
+        Err(e) => return vec![CheckResult::new("schedules", CheckStatus::Fail, e.to_string())],
+    };
+    let runs = match RunLog::open().runs() {
+        Ok(runs) => runs,
+        Err(e) => return vec![CheckResult::new("schedules", CheckStatus::Fail, format!("无法读取执行记录: {}", e))],
+    };
+
+    let now = Utc::now();
+    schedules
+        .iter()
+        .map(|schedule| {
+            let name = format!("schedule:{}", schedule.config.name);
+            let job_runs: Vec<_> = runs.iter().filter(|run| run.job == schedule.config.name).collect();
+            let last_success = job_runs
+                .iter()
+                .rev()
+                .find(|run| run.status != TaskRunStatus::Failed && run.error.is_none());
+
+            let Some(last_success) = last_success else {
+                return if job_runs.is_empty() {
+                    CheckResult::new(name, CheckStatus::Warn, "尚无执行记录")
+                } else {
+                    CheckResult::new(name, CheckStatus::Fail, format!("执行了 {} 次，从未成功", job_runs.len()))
+                };
+            };
+
+            let since = last_success.started_at;
+            let missed = schedule.missed_runs(since, now - Duration::minutes(SCHEDULE_GRACE_MINUTES));
+            let message = format!(
+                "上次成功执行于 {}",
+                since.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+            match missed {
+                0 => CheckResult::new(name, CheckStatus::Ok, message),
+                1 => CheckResult::new(name, CheckStatus::Warn, format!("{}，之后错过了 1 次执行", message)),
+                missed => CheckResult::new(name, CheckStatus::Fail, format!("{}，之后错过了 {} 次执行", message, missed)),
+            }
+        })
+        .collect()
+}
+
+/// LLM 接口可访问且密钥有效，结果缓存一段时间
+async fn check_llm(config: &Config) -> CheckResult {
+    if let Some((checked, result)) = LLM_PROBE_CACHE.lock().unwrap().as_ref() {
+        if checked.elapsed().as_secs() < LLM_PROBE_TTL_SECS {
+            return result.clone();
+        }
+    }
+
+    let result = match DailyGenerator::probe_api(&config.llm).await {
+        Ok(elapsed) => CheckResult::new(
+            "llm",
+            CheckStatus::Ok,
+            format!("DeepSeek API 可访问（{} ms）", elapsed.as_millis()),
+        ),
+        Err(e) => CheckResult::new("llm", CheckStatus::Fail, format!("DeepSeek API 不可用: {}", e)),
+    };
+    *LLM_PROBE_CACHE.lock().unwrap() = Some((Instant::now(), result.clone()));
+    result
+}
+
+/// 每个 RSS 源的连续失败次数和暂停状态
+///
+/// 第三方源不可用时其他源照常获取，因此最多只给出警告，不会让健康检查失败。
+fn check_sources(config: &Config) -> Vec<CheckResult> {
+    let statuses = match SourceStatusStore::load() {
+        Ok(statuses) => statuses,
+        Err(e) => return vec![CheckResult::new("sources", CheckStatus::Fail, format!("无法读取 RSS 源状态: {}", e))],
+    };
+    let policy = &config.fetch;
+
+    config
+        .sources
+        .iter()
+        .map(|source| {
+            let name = format!("source:{}", source.name);
+            let Some(status) = statuses.current(source) else {
+                return CheckResult::new(name, CheckStatus::Ok, "尚未获取");
+            };
+            if status.quarantined_at.is_some() {
+                return CheckResult::new(
+                    name,
+                    CheckStatus::Warn,
+                    format!(
+                        "连续失败 {} 次，已暂停获取，{}自动重试，或运行 rust-daily --resume-source \"{}\" 立即恢复: {}",
+                        status.consecutive_failures,
+                        status
+                            .next_retry(policy)
+                            .map(|time| format!("将于 {} ", time.format("%Y-%m-%d %H:%M:%S UTC")))
+                            .unwrap_or_default(),
+                        source.name,
+                        status.last_error.as_deref().unwrap_or("未知错误")
+                    ),
+                );
+            }
+
+            match status.consecutive_failures {
+                0 => CheckResult::new(
+                    name,
+                    CheckStatus::Ok,
+                    match status.last_success {
+                        Some(time) => format!("上次成功获取于 {}", time.format("%Y-%m-%d %H:%M:%S UTC")),
+                        None => "正常".to_string(),
+                    },
+                ),
+                failures => CheckResult::new(
+                    name,
+                    CheckStatus::Warn,
+                    match policy.quarantine_after {
+                        0 => format!(
+                            "连续失败 {} 次: {}",
+                            failures,
+                            status.last_error.as_deref().unwrap_or("未知错误")
+                        ),
+                        quarantine_after => format!(
+                            "连续失败 {}/{} 次，达到后暂停获取: {}",
+                            failures,
+                            quarantine_after,
+                            status.last_error.as_deref().unwrap_or("未知错误")
+                        ),
+                    },
+                ),
+            }
+        })
+        .collect()
+}
@@ -4,23 +4,59 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
+use crate::config::RetentionPolicy;
+use crate::report_store::{write_atomic, ReportStore};
 use crate::NewsItem;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessedItem {
-    pub id: String,           // 新闻唯一标识 (URL hash)
+    pub id: String,           // 新闻唯一标识 (链接和标题的 SHA-256)
     pub title: String,        // 新闻标题
     pub url: String,          // 新闻链接
     pub processed_at: DateTime<Utc>, // 处理时间
     pub source: String,       // 来源
+    #[serde(default)]
+    pub summary: Option<String>,     // AI 生成的摘要
+    #[serde(default)]
+    pub reports: Vec<ReportRef>,     // 收录该新闻的日报
+}
+
+/// 日报引用，记录新闻被收录到哪一份日报
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportRef {
+    #[serde(default)]
+    pub id: String,                  // 日报 ID
+    pub file: String,                // 日报文件名（相对于报告目录）
+    pub date: DateTime<Utc>,         // 日报生成时间
+}
+
+/// 尚未提交的生成任务：日报写入成功后才会把其中的新闻标记为已处理
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingRun {
+    pub run_id: String,
+    pub started_at: DateTime<Utc>,
+    pub report: ReportRef,
+    pub items: Vec<NewsItem>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProcessingHistory {
     pub items: Vec<ProcessedItem>,
     pub last_cleanup: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub pending_runs: Vec<PendingRun>,
+}
+
+/// 历史记录完整性检查结果
+#[derive(Debug, Default)]
+pub struct HistoryIntegrity {
+    pub items: usize,
+    /// 尚未提交的生成任务数
+    pub pending_runs: usize,
+    /// 被引用但文件已不存在的日报数
+    pub missing_reports: usize,
 }
 
 pub struct HistoryManager {
@@ -57,8 +93,12 @@ impl HistoryManager {
     fn load_history(path: &PathBuf) -> Result<ProcessingHistory> {
         if path.exists() {
             let content = fs::read_to_string(path)?;
-            let history: ProcessingHistory = serde_json::from_str(&content)
+            let mut history: ProcessingHistory = serde_json::from_str(&content)
                 .unwrap_or_default();
+            // 旧版本的标识依赖标准库哈希，随 Rust 版本变化，统一按链接和标题重新计算
+            for item in &mut history.items {
+                item.id = NewsItem::id_for(&item.url, &item.title);
+            }
             info!("加载历史记录，包含 {} 条已处理项目", history.items.len());
             Ok(history)
         } else {
@@ -67,24 +107,39 @@ impl HistoryManager {
         }
     }
     
+    /// 严格检查历史记录文件，文件损坏时返回错误（`new()` 会静默使用空记录）
+    pub fn check_integrity(store: &ReportStore) -> Result<HistoryIntegrity> {
+        let path = Self::get_history_file_path();
+        if !path.exists() {
+            return Ok(HistoryIntegrity::default());
+        }
+        
+        let content = fs::read_to_string(&path)?;
+        let history: ProcessingHistory = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("历史记录文件 {} 已损坏: {}", path.display(), e))?;
+        
+        let mut files: HashSet<&str> = HashSet::new();
+        for item in &history.items {
+            for report in &item.reports {
+                files.insert(report.file.as_str());
+            }
+        }
+        let missing_reports = files.iter().filter(|file| !store.exists(file)).count();
+        
+        Ok(HistoryIntegrity {
+            items: history.items.len(),
+            pending_runs: history.pending_runs.len(),
+            missing_reports,
+        })
+    }
+    
     fn save_history(&self) -> Result<()> {
         let content = serde_json::to_string_pretty(&self.history)?;
-        fs::write(&self.history_file, content)?;
+        write_atomic(&self.history_file, content.as_bytes())?;
         debug!("保存历史记录到: {:?}", self.history_file);
         Ok(())
     }
     
-    /// 生成新闻项的唯一标识
-    fn generate_item_id(item: &NewsItem) -> String {
-        use std::hash::{Hash, Hasher};
-        use std::collections::hash_map::DefaultHasher;
-        
-        let mut hasher = DefaultHasher::new();
-        item.link.hash(&mut hasher);
-        item.title.hash(&mut hasher);
-        format!("{:x}", hasher.finish())
-    }
-    
     /// 过滤掉已处理的新闻项
     pub fn filter_unprocessed(&self, items: Vec<NewsItem>) -> Vec<NewsItem> {
         let processed_ids: HashSet<String> = self.history.items
@@ -96,7 +151,7 @@ impl HistoryManager {
         let filtered_items: Vec<NewsItem> = items
             .into_iter()
             .filter(|item| {
-                let id = Self::generate_item_id(item);
+                let id = item.id();
                 !processed_ids.contains(&id)
             })
             .collect();
@@ -114,25 +169,109 @@ impl HistoryManager {
         filtered_items
     }
     
-    /// 标记新闻项为已处理
-    pub fn mark_as_processed(&mut self, items: &[NewsItem]) -> Result<()> {
+    /// 开始一次生成任务：把日报收录的新闻登记为待提交状态
+    ///
+    /// 必须在写入日报文件之前调用，日报写入成功后再调用 [`commit_run`](Self::commit_run)。
+    pub fn begin_run(&mut self, run_id: &str, items: &[NewsItem], report: &ReportRef) -> Result<()> {
+        self.history.pending_runs.push(PendingRun {
+            run_id: run_id.to_string(),
+            started_at: Utc::now(),
+            report: report.clone(),
+            items: items.to_vec(),
+        });
+        self.save_history()?;
+        debug!("登记待提交任务 {}，包含 {} 条新闻", run_id, items.len());
+        Ok(())
+    }
+    
+    /// 提交生成任务：日报已写入，把待提交的新闻标记为已处理
+    pub fn commit_run(&mut self, run_id: &str) -> Result<()> {
+        let Some(index) = self.history.pending_runs.iter().position(|run| run.run_id == run_id) else {
+            return Err(anyhow::anyhow!("未找到待提交的任务: {}", run_id));
+        };
+        
+        let run = self.history.pending_runs.remove(index);
+        self.mark_as_processed(&run.items, &run.report)
+    }
+    
+    /// 放弃生成任务：日报写入失败，待提交的新闻保持未处理状态
+    pub fn abort_run(&mut self, run_id: &str) -> Result<()> {
+        let original_count = self.history.pending_runs.len();
+        self.history.pending_runs.retain(|run| run.run_id != run_id);
+        
+        if self.history.pending_runs.len() != original_count {
+            self.save_history()?;
+            warn!("已放弃任务 {}，相关新闻将在下次运行时重新处理", run_id);
+        }
+        Ok(())
+    }
+    
+    /// 恢复上次异常退出遗留的待提交任务
+    ///
+    /// 日报文件已存在的任务会被提交，否则放弃。返回 (提交数, 放弃数)。
+    pub fn recover_pending_runs(&mut self, store: &ReportStore) -> Result<(usize, usize)> {
+        if self.history.pending_runs.is_empty() {
+            return Ok((0, 0));
+        }
+        
+        let pending_runs = std::mem::take(&mut self.history.pending_runs);
+        let mut committed = 0;
+        let mut discarded = 0;
+        
+        for run in pending_runs {
+            if store.exists(&run.report.file) {
+                info!("恢复任务 {}: 日报 {} 已存在，提交 {} 条新闻", run.run_id, run.report.file, run.items.len());
+                self.record_processed(&run.items, &run.report);
+                committed += 1;
+            } else {
+                warn!("恢复任务 {}: 日报 {} 不存在，放弃 {} 条新闻", run.run_id, run.report.file, run.items.len());
+                discarded += 1;
+            }
+        }
+        
+        self.save_history()?;
+        Ok((committed, discarded))
+    }
+    
+    /// 标记新闻项为已处理，并记录收录它们的日报
+    fn mark_as_processed(&mut self, items: &[NewsItem], report: &ReportRef) -> Result<()> {
+        self.record_processed(items, report);
+        info!("标记 {} 条新闻为已处理，收录于日报 {}", items.len(), report.file);
+        self.save_history()?;
+        Ok(())
+    }
+    
+    /// 写入已处理记录（不保存到磁盘）
+    ///
+    /// 已存在的记录不会重复添加，只会追加日报引用并更新摘要。
+    fn record_processed(&mut self, items: &[NewsItem], report: &ReportRef) {
         let now = Utc::now();
         
         for item in items {
+            let id = item.id();
+            
+            if let Some(existing) = self.history.items.iter_mut().find(|record| record.id == id) {
+                if !existing.reports.contains(report) {
+                    existing.reports.push(report.clone());
+                }
+                if item.ai_summary.is_some() {
+                    existing.summary = item.ai_summary.clone();
+                }
+                continue;
+            }
+            
             let processed_item = ProcessedItem {
-                id: Self::generate_item_id(item),
+                id,
                 title: item.title.clone(),
                 url: item.link.clone(),
                 processed_at: now,
                 source: item.source.clone(),
+                summary: item.ai_summary.clone(),
+                reports: vec![report.clone()],
             };
             
             self.history.items.push(processed_item);
         }
-        
-        info!("标记 {} 条新闻为已处理", items.len());
-        self.save_history()?;
-        Ok(())
     }
     
     /// 清理过期的历史记录
@@ -153,6 +292,43 @@ impl HistoryManager {
         Ok(removed_count)
     }
     
+    /// 按保留策略自动清理历史记录
+    ///
+    /// 通过 `last_cleanup` 保证每天最多执行一次，返回删除的记录数。
+    pub fn apply_retention(&mut self, policy: &RetentionPolicy) -> Result<usize> {
+        let now = Utc::now();
+        if let Some(last_cleanup) = self.history.last_cleanup {
+            if now - last_cleanup < Duration::days(1) {
+                debug!("上次清理于 {}，跳过本次自动清理", last_cleanup);
+                return Ok(0);
+            }
+        }
+        
+        let original_count = self.history.items.len();
+        
+        if policy.max_age_days > 0 {
+            let cutoff_date = now - Duration::days(policy.max_age_days as i64);
+            self.history.items.retain(|item| item.processed_at >= cutoff_date);
+        }
+        
+        if policy.max_records > 0 && self.history.items.len() > policy.max_records {
+            self.history.items.sort_by_key(|item| item.processed_at);
+            let excess = self.history.items.len() - policy.max_records;
+            self.history.items.drain(..excess);
+        }
+        
+        let removed_count = original_count - self.history.items.len();
+        self.history.last_cleanup = Some(now);
+        self.save_history()?;
+        
+        if removed_count > 0 {
+            info!("按保留策略清理了 {} 条历史记录（最长 {} 天，最多 {} 条）",
+                  removed_count, policy.max_age_days, policy.max_records);
+        }
+        
+        Ok(removed_count)
+    }
+    
     /// 获取统计信息
     pub fn get_stats(&self) -> HistoryStats {
         let now = Utc::now();
@@ -197,6 +373,71 @@ impl HistoryManager {
         Ok(())
     }
     
+    /// 按 ID 获取单条历史记录
+    pub fn get(&self, id: &str) -> Option<&ProcessedItem> {
+        self.history.items.iter().find(|item| item.id == id)
+    }
+    
+    /// 获取全部历史记录（按处理时间排列）
+    pub fn items(&self) -> &[ProcessedItem] {
+        &self.history.items
+    }
+    
+    /// 取消已处理标记，使匹配的新闻在下次运行时重新处理
+    ///
+    /// `key` 可以是记录 ID 或新闻链接，返回被移除的记录数。
+    pub fn forget(&mut self, key: &str) -> Result<usize> {
+        let original_count = self.history.items.len();
+        self.history.items.retain(|item| item.id != key && item.url != key);
+        
+        let removed_count = original_count - self.history.items.len();
+        if removed_count > 0 {
+            self.save_history()?;
+            info!("取消了 {} 条记录的已处理标记: {}", removed_count, key);
+        }
+        
+        Ok(removed_count)
+    }
+    
+    /// 删除日报后移除记录中对它的引用，新闻仍保持已处理状态，返回受影响的记录数
+    pub fn remove_report(&mut self, report_id: &str) -> Result<usize> {
+        let mut affected = 0;
+        for item in &mut self.history.items {
+            let original_count = item.reports.len();
+            item.reports.retain(|report| report.id != report_id);
+            if item.reports.len() != original_count {
+                affected += 1;
+            }
+        }
+        
+        if affected > 0 {
+            self.save_history()?;
+            info!("从 {} 条记录中移除了日报 {} 的引用", affected, report_id);
+        }
+        
+        Ok(affected)
+    }
+    
+    /// 反向查找：列出收录过指定链接的所有日报
+    pub fn reports_for_url(&self, url: &str) -> Vec<&ReportRef> {
+        let mut reports: Vec<&ReportRef> = self.history.items
+            .iter()
+            .filter(|item| item.url == url)
+            .flat_map(|item| item.reports.iter())
+            .collect();
+        reports.sort_by_key(|report| report.date);
+        reports.dedup();
+        reports
+    }
+    
+    /// 按关键词搜索并应用来源、日期过滤条件
+    pub fn search_filtered(&self, query: &str, filter: &HistoryFilter) -> Vec<&ProcessedItem> {
+        self.search(query)
+            .into_iter()
+            .filter(|item| filter.matches(item))
+            .collect()
+    }
+    
     /// 搜索历史记录
     pub fn search(&self, query: &str) -> Vec<&ProcessedItem> {
         let query_lower = query.to_lowercase();
@@ -211,6 +452,114 @@ impl HistoryManager {
     }
 }
 
+impl ProcessedItem {
+    /// 显示单条记录的详细信息
+    pub fn display(&self) {
+        println!("🔖 历史记录详情:");
+        println!("  ID: {}", self.id);
+        println!("  标题: {}", self.title);
+        println!("  链接: {}", self.url);
+        println!("  来源: {}", self.source);
+        println!("  处理时间: {}", self.processed_at.format("%Y-%m-%d %H:%M:%S UTC"));
+        
+        if self.reports.is_empty() {
+            println!("  收录日报: 无记录");
+        } else {
+            println!("  收录日报:");
+            for report in &self.reports {
+                println!("    - {} ({}, {})", report.id, report.file, report.date.format("%Y-%m-%d %H:%M:%S UTC"));
+            }
+        }
+        
+        if let Some(summary) = &self.summary {
+            println!("  摘要:");
+            for line in summary.lines() {
+                println!("    {}", line);
+            }
+        }
+    }
+}
+
+/// 以表格形式输出历史记录
+pub fn print_items_table(items: &[&ProcessedItem]) {
+    println!("{:<16}  {:<16}  {:<20}  标题", "ID", "处理时间", "来源");
+    for item in items {
+        println!("{:<16}  {:<16}  {:<20}  {}",
+            item.id,
+            item.processed_at.format("%Y-%m-%d %H:%M"),
+            truncate(&item.source, 20),
+            truncate(&item.title, 60)
+        );
+    }
+    println!("共 {} 条记录", items.len());
+}
+
+/// 将历史记录导出为 CSV（RFC 4180）
+pub fn items_to_csv(items: &[&ProcessedItem]) -> String {
+    let mut output = String::from("id,title,url,source,processed_at,reports\n");
+    for item in items {
+        let reports: Vec<&str> = item.reports.iter().map(|report| report.file.as_str()).collect();
+        let fields = [
+            item.id.as_str(),
+            item.title.as_str(),
+            item.url.as_str(),
+            item.source.as_str(),
+            &item.processed_at.to_rfc3339(),
+            &reports.join(";"),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_escape(field)).collect();
+        output.push_str(&row.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() > max_chars {
+        let truncated: String = text.chars().take(max_chars - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        text.to_string()
+    }
+}
+
+/// 历史记录过滤条件
+#[derive(Debug, Default)]
+pub struct HistoryFilter {
+    pub source: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, item: &ProcessedItem) -> bool {
+        if let Some(source) = &self.source {
+            if !item.source.eq_ignore_ascii_case(source) {
+                return false;
+            }
+        }
+        if let Some(since) = self.since {
+            if item.processed_at < since {
+                return false;
+            }
+        }
+        if let Some(until) = self.until {
+            if item.processed_at >= until {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 #[derive(Debug)]
 pub struct HistoryStats {
     pub total_processed: usize,
@@ -1,24 +1,40 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use clap::{Arg, Command};
-use rig::prelude::*;
-use rig::providers::deepseek;
 use serde::{Deserialize, Serialize};
-use tracing::{info, warn};
+use std::path::PathBuf;
+use tracing::{error, info, warn};
 
+mod admin;
+mod api;
 mod rss_parser;
 mod daily_generator;
 mod config;
+mod health;
 mod history;
+mod markdown;
+mod pipeline;
+mod report_page;
+mod report_store;
 mod rss_server;
+mod run_lock;
+mod run_log;
 mod scheduler;
+mod source_status;
+mod state;
+mod telemetry;
 
 use rss_parser::RssFetcher;
 use daily_generator::DailyGenerator;
-use config::Config;
-use history::HistoryManager;
-use rss_server::{RssServer, RssServerConfig};
+use config::{Config, ConfigOverrides};
+use history::{HistoryFilter, HistoryManager};
+use pipeline::{RunOptions, RunOutcome};
+use report_store::ReportStore;
+use rss_server::{ReloadSignal, RssServer, RssServerConfig};
+use run_lock::RunLock;
 use scheduler::TaskScheduler;
+use source_status::SourceStatusStore;
+use state::SharedState;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewsItem {
@@ -27,13 +43,68 @@ pub struct NewsItem {
     pub description: String,
     pub pub_date: Option<DateTime<Utc>>,
     pub source: String,
+    /// 单条处理模式下 AI 为该新闻生成的摘要
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_summary: Option<String>,
+    /// 分类标签，来自 RSS 条目自带的分类和源配置中的 `tags`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl NewsItem {
+    /// 新闻的稳定标识，由链接和标题计算，历史记录和单条新闻 feed 共用
+    pub fn id(&self) -> String {
+        Self::id_for(&self.link, &self.title)
+    }
+
+    /// 由链接和标题计算标识：SHA-256 的前 8 字节，不随 Rust 版本变化，可作为 feed GUID 发布
+    pub fn id_for(link: &str, title: &str) -> String {
+        use sha2::{Digest, Sha256};
+        
+        let mut hasher = Sha256::new();
+        hasher.update(link.as_bytes());
+        hasher.update([0]);
+        hasher.update(title.as_bytes());
+        hasher.finalize()[..8].iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyReport {
+    /// 日报唯一标识，格式为 `YYYY-MM-DD-NN`，保存时由 `ReportStore` 分配
+    #[serde(default)]
+    pub id: String,
+    /// 当天的期数，从 1 开始
+    #[serde(default)]
+    pub edition: u32,
     pub date: DateTime<Utc>,
     pub items: Vec<NewsItem>,
     pub summary: String,
+    /// 生成时的提示，例如因连续失败被暂停获取的 RSS 源
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notices: Vec<String>,
+}
+
+impl DailyReport {
+    pub fn new(items: Vec<NewsItem>, summary: String) -> Self {
+        Self {
+            id: String::new(),
+            edition: 0,
+            date: Utc::now(),
+            items,
+            summary,
+            notices: Vec::new(),
+        }
+    }
+    
+    /// 日报标题，同一天的第二期起附带期数
+    pub fn title(&self) -> String {
+        if self.edition > 1 {
+            format!("【Rust日报】{} 第 {} 期", self.date.format("%Y-%m-%d"), self.edition)
+        } else {
+            format!("【Rust日报】{}", self.date.format("%Y-%m-%d"))
+        }
+    }
 }
 
 #[tokio::main]
@@ -47,6 +118,19 @@ async fn main() -> Result<()> {
     // 解析命令行参数
     let matches = Command::new("rust-daily")
         .about("Rust 每日新闻摘要生成器")
+        .arg(
+            Arg::new("config")
+                .short('c')
+                .long("config")
+                .value_name("PATH")
+                .help("配置文件路径 (默认: RUST_DAILY_CONFIG、./rust-daily.toml 或 ./rss_sources.toml)")
+        )
+        .arg(
+            Arg::new("print-config")
+                .long("print-config")
+                .help("显示合并默认值、配置文件、环境变量和命令行参数后生效的配置")
+                .action(clap::ArgAction::SetTrue)
+        )
         .arg(
             Arg::new("output")
                 .short('o')
@@ -59,8 +143,8 @@ async fn main() -> Result<()> {
                 .short('d')
                 .long("days") 
                 .value_name("DAYS")
-                .default_value("1")
-                .help("获取最近几天的新闻 (默认: 1)")
+                .value_parser(clap::value_parser!(u64))
+                .help("获取最近几天的新闻 (默认: [fetch] days 或 1)")
         )
         .arg(
             Arg::new("force")
@@ -114,15 +198,27 @@ async fn main() -> Result<()> {
         .arg(
             Arg::new("serve")
                 .long("serve")
-                .help("启动 RSS 服务器")
+                .help("启动 RSS 服务器（与 --daemon 同时使用时在同一进程内运行定时任务）")
                 .action(clap::ArgAction::SetTrue)
         )
         .arg(
             Arg::new("port")
                 .long("port")
                 .value_name("PORT")
-                .default_value("7080")
-                .help("RSS 服务器端口 (默认: 7080)")
+                .value_parser(clap::value_parser!(u16))
+                .help("RSS 服务器端口 (默认: RSS_PORT、[server] port 或 7080)")
+        )
+        .arg(
+            Arg::new("host")
+                .long("host")
+                .value_name("HOST")
+                .help("RSS 服务器监听地址，支持 IPv4/IPv6/主机名 (默认: RSS_HOST、[server] host 或 127.0.0.1)")
+        )
+        .arg(
+            Arg::new("unix-socket")
+                .long("unix-socket")
+                .value_name("PATH")
+                .help("RSS 服务器改为监听 Unix domain socket (默认: RSS_UNIX_SOCKET 或 [server] unix_socket)")
         )
         .arg(
             Arg::new("list-sources")
@@ -139,7 +235,7 @@ async fn main() -> Result<()> {
         .arg(
             Arg::new("daemon")
                 .long("daemon")
-                .help("守护进程模式：每 4 小时自动生成日报")
+                .help("守护进程模式：按配置文件 [[scheduler.jobs]] 中的 cron 计划自动生成日报，未配置时默认 UTC 每 4 小时一次（可与 --serve 组合）")
                 .action(clap::ArgAction::SetTrue)
         )
         .arg(
@@ -148,36 +244,146 @@ async fn main() -> Result<()> {
                 .help("显示定时任务执行统计")
                 .action(clap::ArgAction::SetTrue)
         )
+        .arg(
+            Arg::new("source-report")
+                .long("source-report")
+                .help("显示每个 RSS 源的获取状况，列出失败、已暂停和长期没有新内容的源")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("resume-source")
+                .long("resume-source")
+                .value_name("NAME")
+                .help("解除 RSS 源的暂停状态，下次生成日报时立即重新获取")
+        )
+        .arg(
+            Arg::new("check-config")
+                .long("check-config")
+                .help("检查配置文件的格式、RSS 源、定时任务和环境变量，有问题时以非零状态退出")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("probe")
+                .long("probe")
+                .help("配合 --check-config 使用：请求每个 RSS 源并报告检测到的 feed 格式")
+                .requires("check-config")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("doctor")
+                .long("doctor")
+                .help("检查报告目录、历史记录、定时任务、LLM 接口和 RSS 源的健康状况，有失败项时以非零状态退出")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .subcommand(
+            Command::new("history")
+                .about("查询和管理历史记录")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("search")
+                        .about("按关键词搜索历史记录（匹配标题、来源和链接）")
+                        .arg(Arg::new("query").required(true).help("搜索关键词"))
+                        .arg(Arg::new("source").long("source").value_name("NAME").help("只显示指定来源的记录"))
+                        .arg(Arg::new("since").long("since").value_name("YYYY-MM-DD").help("只显示该日期及之后处理的记录"))
+                        .arg(Arg::new("until").long("until").value_name("YYYY-MM-DD").help("只显示该日期及之前处理的记录"))
+                        .arg(history_format_arg())
+                )
+                .subcommand(
+                    Command::new("show")
+                        .about("显示单条历史记录详情")
+                        .arg(Arg::new("id").required(true).help("记录 ID"))
+                        .arg(history_format_arg())
+                )
+                .subcommand(
+                    Command::new("reports")
+                        .about("列出收录过指定新闻链接的日报")
+                        .arg(Arg::new("url").required(true).help("新闻链接"))
+                        .arg(history_format_arg())
+                )
+                .subcommand(
+                    Command::new("forget")
+                        .about("取消已处理标记，使新闻在下次运行时重新处理")
+                        .arg(Arg::new("key").required(true).value_name("ID|URL").help("记录 ID 或新闻链接"))
+                )
+                .subcommand(
+                    Command::new("export")
+                        .about("导出全部历史记录")
+                        .arg(
+                            Arg::new("format")
+                                .long("format")
+                                .value_name("FORMAT")
+                                .value_parser(["json", "csv"])
+                                .default_value("json")
+                                .help("导出格式")
+                        )
+                        .arg(
+                            Arg::new("output")
+                                .short('o')
+                                .long("output")
+                                .value_name("FILE")
+                                .help("输出文件路径 (可选，默认输出到控制台)")
+                        )
+                )
+        )
         .get_matches();
     
+    // 命令行参数优先于配置文件和环境变量
+    config::init(
+        matches.get_one::<String>("config").map(PathBuf::from),
+        ConfigOverrides {
+            days: matches.get_one::<u64>("days").copied(),
+            batch: if matches.get_flag("batch-mode") {
+                Some(true)
+            } else if matches.get_flag("single-mode") {
+                Some(false)
+            } else {
+                None
+            },
+            auto_publish: matches.get_flag("publish").then_some(true),
+            host: matches.get_one::<String>("host").cloned(),
+            port: matches.get_one::<u16>("port").copied(),
+            unix_socket: matches.get_one::<String>("unix-socket").map(PathBuf::from),
+        },
+    );
+    
     let output_file = matches.get_one::<String>("output");
-    let days: u64 = matches.get_one::<String>("days")
-        .unwrap()
-        .parse()
-        .unwrap_or(1);
     let force_mode = matches.get_flag("force");
     let show_stats = matches.get_flag("stats");
     let cleanup_days = matches.get_one::<String>("cleanup");
     let clear_history = matches.get_flag("clear-history");
-    let _single_mode = matches.get_flag("single-mode");
-    let batch_mode = matches.get_flag("batch-mode");
-    let publish_mode = matches.get_flag("publish");
     let test_connection = matches.get_flag("test-connection");
     let serve_mode = matches.get_flag("serve");
-    let port: u16 = matches.get_one::<String>("port")
-        .unwrap()
-        .parse()
-        .unwrap_or(7080);
     let list_sources = matches.get_flag("list-sources");
     let add_source = matches.get_one::<String>("add-source");
     let daemon_mode = matches.get_flag("daemon");
     let task_stats = matches.get_flag("task-stats");
+    let doctor = matches.get_flag("doctor");
+    let source_report = matches.get_flag("source-report");
+    let check_config = matches.get_flag("check-config");
     
-    // 默认使用单条处理模式，除非明确指定批量模式
-    let use_single_processing = !batch_mode;
+    // 处理历史记录查询命令
+    if let Some(("history", history_matches)) = matches.subcommand() {
+        return run_history_command(history_matches);
+    }
     
-    // 初始化历史记录管理器
-    let mut history_manager = HistoryManager::new()?;
+    if matches.get_flag("print-config") {
+        let config = Config::resolve()?;
+        println!("# 生效的配置：默认值 < 配置文件 < 环境变量 < 命令行参数");
+        match &config.path {
+            Some(path) => println!("# 配置文件: {}", path.display()),
+            None => println!("# 配置文件: 无（使用默认值）"),
+        }
+        println!();
+        print!("{}", config.to_display_toml()?);
+        return Ok(());
+    }
+    
+    if check_config {
+        if !run_check_config(matches.get_flag("probe")).await {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
     
     // 处理 RSS 源管理命令
     if list_sources {
@@ -186,7 +392,7 @@ async fn main() -> Result<()> {
         println!("┌─────┬─────────────────────────────┬─────────────────────────────────────────────────────┐");
         println!("│ 序号 │           名称              │                     URL                             │");
         println!("├─────┼─────────────────────────────┼─────────────────────────────────────────────────────┤");
-        for (i, source) in config.rss_sources.iter().enumerate() {
+        for (i, source) in config.sources.iter().enumerate() {
             println!("│ {:3} │ {:27} │ {:51} │", 
                 i + 1, 
                 if source.name.len() > 27 { &source.name[..24] } else { &source.name },
@@ -194,7 +400,31 @@ async fn main() -> Result<()> {
             );
         }
         println!("└─────┴─────────────────────────────┴─────────────────────────────────────────────────────┘");
-        println!("💡 编辑 rss_sources.toml 文件来添加或修改 RSS 源");
+        println!(
+            "💡 编辑 {} 文件来添加或修改 RSS 源",
+            config.path.as_deref().unwrap_or(std::path::Path::new(config::CONFIG_FILE)).display()
+        );
+        return Ok(());
+    }
+    
+    if source_report {
+        let config = Config::load()?;
+        SourceStatusStore::load()?.display_report(&config);
+        return Ok(());
+    }
+    
+    if let Some(name) = matches.get_one::<String>("resume-source") {
+        let mut statuses = SourceStatusStore::load()?;
+        if statuses.get(name).is_none() {
+            eprintln!("❌ 没有 RSS 源「{}」的获取记录，请用 --source-report 查看源名称", name);
+            std::process::exit(1);
+        }
+        if statuses.resume(name) {
+            statuses.save()?;
+            println!("✅ 已恢复 RSS 源「{}」，下次生成日报时会重新获取", name);
+        } else {
+            println!("💡 RSS 源「{}」没有被暂停，无需恢复", name);
+        }
         return Ok(());
     }
     
@@ -214,27 +444,44 @@ async fn main() -> Result<()> {
             return Ok(());
         }
         
-        Config::add_source_to_file(&name, &url)?;
-        println!("✅ 已添加 RSS 源: {} -> {}", name, url);
+        let path = Config::add_source_to_file(&name, &url)?;
+        println!("✅ 已添加 RSS 源: {} -> {}（{}）", name, url, path.display());
         println!("💡 重新运行程序来使用新的 RSS 源");
         return Ok(());
     }
     
+    // 同时启动定时任务和 RSS 服务器
+    if serve_mode && daemon_mode {
+        info!("启动组合模式 - 定时任务调度器 + RSS 服务器");
+        
+        let config = Config::load()?;
+        let reports_dir = config.generation.reports_dir.clone();
+        std::fs::create_dir_all(&reports_dir)?;
+        
+        // 调度器和服务器共享生成锁，新日报生成后服务器缓存立即失效
+        let shared = SharedState::default();
+        let server = RssServer::new(RssServerConfig::from_config(&config.server), reports_dir.clone())
+            .with_shared_state(shared.clone())
+            .with_local_scheduler();
+        let scheduler = TaskScheduler::new(reports_dir).await?
+            .with_shared_state(shared);
+        spawn_server_reload(server.clone());
+        
+        return run_combined(server, scheduler).await;
+    }
+    
     // 处理 RSS 服务器模式
     if serve_mode {
         info!("启动 RSS 服务器模式");
         
         // 创建报告目录
-        let reports_dir = std::env::var("REPORTS_DIR")
-            .unwrap_or_else(|_| "./reports".to_string());
+        let config = Config::load()?;
+        let reports_dir = config.generation.reports_dir.clone();
         std::fs::create_dir_all(&reports_dir)?;
         
-        // 创建服务器配置
-        let mut config = RssServerConfig::from_env();
-        config.port = port;
-        
         // 启动服务器
-        let server = RssServer::new(config, reports_dir);
+        let server = RssServer::new(RssServerConfig::from_config(&config.server), reports_dir);
+        spawn_server_reload(server.clone());
         server.start().await?;
         return Ok(());
     }
@@ -244,42 +491,44 @@ async fn main() -> Result<()> {
         info!("启动守护进程模式 - 定时任务调度器");
         
         // 创建报告目录
-        let reports_dir = std::env::var("REPORTS_DIR")
-            .unwrap_or_else(|_| "./reports".to_string());
+        let config = Config::load()?;
+        let reports_dir = config.generation.reports_dir;
         std::fs::create_dir_all(&reports_dir)?;
         
-        // 创建并启动任务调度器
-        let scheduler = TaskScheduler::new(reports_dir).await?;
-        
-        // 使用 Ctrl+C 信号处理来优雅关闭
-        let scheduler_shutdown = std::sync::Arc::new(tokio::sync::Mutex::new(scheduler));
-        let scheduler_clone = scheduler_shutdown.clone();
-        
-        tokio::spawn(async move {
-            tokio::signal::ctrl_c().await.expect("Failed to listen for ctrl+c");
-            info!("收到停止信号，正在优雅关闭...");
-            
-            let mut scheduler = scheduler_clone.lock().await;
-            if let Err(e) = scheduler.shutdown().await {
-                warn!("关闭调度器时出错: {}", e);
-            }
-            
-            std::process::exit(0);
-        });
+        // 记录定时任务的指标，配置了 metrics_listen 时提供 /metrics
+        telemetry::install();
+        if let Some(addr) = config.scheduler.metrics_listen {
+            telemetry::serve(addr).await?;
+        }
         
-        // 启动调度器（这会阻塞直到程序结束）
-        let mut scheduler = scheduler_shutdown.lock().await;
+        // 启动调度器，收到 Ctrl+C 或 SIGTERM 后优雅关闭
+        let mut scheduler = TaskScheduler::new(reports_dir).await?;
         scheduler.start().await?;
+        scheduler.run_until(rss_server::shutdown_signal()).await?;
+        return Ok(());
+    }
+    
+    // 处理健康检查
+    if doctor {
+        let config = Config::load()?;
+        let report = health::run_checks(
+            &config,
+            &ReportStore::from_config(&config),
+            health::CheckOptions { probe_llm: true, schedules: true },
+        ).await;
+        report.display();
+        if !report.is_healthy() {
+            std::process::exit(1);
+        }
         return Ok(());
     }
     
     // 处理任务统计
     if task_stats {
         // 创建临时调度器来获取统计信息
-        let reports_dir = std::env::var("REPORTS_DIR")
-            .unwrap_or_else(|_| "./reports".to_string());
+        let reports_dir = Config::load()?.generation.reports_dir;
         let scheduler = TaskScheduler::new(reports_dir).await?;
-        let stats = scheduler.get_task_stats().await;
+        let stats = scheduler.get_task_stats().await?;
         stats.display();
         return Ok(());
     }
@@ -295,7 +544,7 @@ async fn main() -> Result<()> {
     
     // 处理管理命令
     if show_stats {
-        let stats = history_manager.get_stats();
+        let stats = HistoryManager::new()?.get_stats();
         stats.display();
         return Ok(());
     }
@@ -306,102 +555,60 @@ async fn main() -> Result<()> {
                 warn!("无效的清理天数，使用默认值 30");
                 30
             });
-        let removed_count = history_manager.cleanup_old_records(cleanup_days)?;
+        let _run_lock = acquire_run_lock()?;
+        let removed_count = HistoryManager::new()?.cleanup_old_records(cleanup_days)?;
         println!("清理完成，删除了 {} 条过期记录", removed_count);
         return Ok(());
     }
     
     if clear_history {
-        history_manager.clear_all()?;
+        let _run_lock = acquire_run_lock()?;
+        HistoryManager::new()?.clear_all()?;
         println!("所有历史记录已清空");
         return Ok(());
     }
     
     info!("启动 Rust 日报生成器");
-    info!("获取最近 {} 天的新闻", days);
-    if force_mode {
-        info!("强制模式：将处理所有新闻，忽略历史记录");
-    }
     
     // 加载配置
     let config = Config::load()?;
-    
-    // 创建 RSS 获取器
-    let rss_fetcher = RssFetcher::new();
-    
-    // 创建 DeepSeek 客户端
-    let deepseek_client = deepseek::Client::from_env();
-    let agent = deepseek_client
-        .agent("deepseek-chat")
-        .preamble("你是 Rust 中文社区的专业技术编辑，负责整理每日 Rust 技术资讯。你需要按照 rustcc.cn 日报的格式和风格，用专业但易懂的中文编写技术日报。重点关注技术细节、实用价值和社区动态，保持客观中性的技术写作风格。")
-        .build();
-    
-    // 创建日报生成器
-    let daily_generator = DailyGenerator::new(agent);
-    
-    // 获取新闻
-    info!("正在获取 RSS 源...");
-    let mut all_news = Vec::new();
-    
-    for rss_url in &config.rss_sources {
-        match rss_fetcher.fetch_rss(&rss_url.url, days).await {
-            Ok(mut items) => {
-                info!("从 {} 获取到 {} 条新闻", rss_url.name, items.len());
-                for item in &mut items {
-                    item.source = rss_url.name.clone();
-                }
-                all_news.extend(items);
-            }
-            Err(e) => {
-                warn!("获取 RSS 源 {} 失败: {}", rss_url.name, e);
-            }
-        }
-    }
-    
-    if all_news.is_empty() {
-        println!("未获取到任何新闻，请检查网络连接或 RSS 源配置");
-        return Ok(());
-    }
-    
-    info!("总共获取到 {} 条新闻", all_news.len());
-    
-    // 过滤已处理的新闻（除非使用强制模式）
-    let filtered_news = if force_mode {
-        all_news
-    } else {
-        history_manager.filter_unprocessed(all_news)
-    };
-    
-    if filtered_news.is_empty() {
-        println!("所有新闻都已处理过，没有新内容生成日报");
-        println!("使用 --force 参数可以强制处理所有新闻");
-        println!("使用 --stats 查看历史记录统计");
-        return Ok(());
+    info!("获取最近 {} 天的新闻", config.fetch.days);
+    if force_mode {
+        info!("强制模式：将处理所有新闻，忽略历史记录");
     }
     
-    // 生成日报
-    info!("正在生成日报摘要...");
-    if use_single_processing {
-        info!("使用单条处理模式，生成详细摘要");
-    } else {
-        info!("使用批量处理模式，生成整体摘要");
-    }
-    let daily_report = daily_generator.generate_daily_report(filtered_news.clone(), use_single_processing).await?;
+    // 与守护进程和其他命令行进程互斥，避免重复处理同一批新闻；
+    // 持有锁之后再加载历史记录，确保看到其他进程已提交的内容
+    let _run_lock = acquire_run_lock()?;
+    let mut history_manager = HistoryManager::new()?;
     
-    // 标记新闻为已处理（除非是强制模式）
-    if !force_mode {
-        history_manager.mark_as_processed(&filtered_news)?;
-    }
+    // 恢复上次异常退出遗留的待提交任务
+    let report_store = ReportStore::from_config(&config);
+    history_manager.recover_pending_runs(&report_store)?;
     
-    // 保存报告到 JSON 文件（用于 RSS 服务器）
-    let reports_dir = std::env::var("REPORTS_DIR")
-        .unwrap_or_else(|_| "./reports".to_string());
-    std::fs::create_dir_all(&reports_dir)?;
+    // 按保留策略清理历史记录（每天最多一次）
+    history_manager.apply_retention(&config.history)?;
     
-    let report_filename = format!("{}/{}.json", reports_dir, daily_report.date.format("%Y-%m-%d"));
-    let report_json = serde_json::to_string_pretty(&daily_report)?;
-    std::fs::write(&report_filename, report_json)?;
-    info!("报告已保存到: {}", report_filename);
+    // 获取新闻、生成日报并保存，写入成功后才标记新闻为已处理（强制模式不标记）
+    // 默认使用单条处理模式，除非明确指定批量模式
+    let options = RunOptions {
+        force: force_mode,
+        batch: config.generation.batch,
+        days: config.fetch.days,
+    };
+    let daily_report = match pipeline::run(&config, &options, &report_store, &mut history_manager).await? {
+        RunOutcome::NoNews => {
+            println!("未获取到任何新闻，请检查网络连接或 RSS 源配置");
+            return Ok(());
+        }
+        RunOutcome::NothingNew { .. } => {
+            println!("所有新闻都已处理过，没有新内容生成日报");
+            println!("使用 --force 参数可以强制处理所有新闻");
+            println!("使用 --stats 查看历史记录统计");
+            return Ok(());
+        }
+        RunOutcome::Generated { report, .. } => report,
+    };
     
     // 输出结果
     if let Some(output_path) = output_file {
@@ -412,7 +619,7 @@ async fn main() -> Result<()> {
     }
     
     // 发布到 Freedit 论坛（现在推荐使用 RSS 集成）
-    if publish_mode || std::env::var("AUTO_PUBLISH").unwrap_or_default() == "true" {
+    if config.generation.auto_publish {
         println!("\n💡 推荐使用 RSS 集成方式发布日报:");
         println!("   1. 启动 RSS 服务器: ./target/debug/rust-daily --serve");
         println!("   2. Freedit 会自动抓取并发布新内容 (每4小时10分钟)");
@@ -424,18 +631,268 @@ async fn main() -> Result<()> {
     if !force_mode {
         let stats = history_manager.get_stats();
         println!("\n📊 本次处理统计:");
-        println!("  新处理: {} 条", filtered_news.len());
+        println!("  新处理: {} 条", daily_report.items.len());
         println!("  总历史: {} 条", stats.total_processed);
     }
     
     Ok(())
 }
 
+/// 收到 SIGHUP 时重新读取配置文件和 `.env` 并应用新的服务器配置，命令行参数仍然优先
+///
+/// 新配置无效时继续使用原配置。
+fn spawn_server_reload(server: RssServer) {
+    tokio::spawn(async move {
+        let mut reload = ReloadSignal::new();
+        loop {
+            reload.recv().await;
+            dotenvy::dotenv_override().ok();
+            match Config::resolve() {
+                Ok(config) => server.reload_config(RssServerConfig::from_config(&config.server)),
+                Err(e) => error!("❌ 新配置无效，RSS 服务器继续使用原配置: {}", e),
+            }
+        }
+    });
+}
+
+/// 在同一进程内运行调度器和 RSS 服务器
+///
+/// 收到停止信号或服务器异常退出时两者一起停止：服务器等待进行中的请求完成，
+/// 调度器等待进行中的日报生成完成。
+async fn run_combined(server: RssServer, mut scheduler: TaskScheduler) -> Result<()> {
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let shutdown_tx = std::sync::Arc::new(shutdown_tx);
+    
+    let wait_for_shutdown = |mut rx: tokio::sync::watch::Receiver<bool>| async move {
+        let _ = rx.wait_for(|stop| *stop).await;
+    };
+    
+    let signal_tx = shutdown_tx.clone();
+    tokio::spawn(async move {
+        rss_server::shutdown_signal().await;
+        let _ = signal_tx.send(true);
+    });
+    
+    scheduler.start().await?;
+    
+    let server_task = async {
+        let result = server.start_with_shutdown(wait_for_shutdown(shutdown_rx.clone())).await;
+        if let Err(e) = &result {
+            warn!("RSS 服务器异常退出，正在停止调度器: {}", e);
+        }
+        let _ = shutdown_tx.send(true);
+        result
+    };
+    let scheduler_task = scheduler.run_until(wait_for_shutdown(shutdown_rx.clone()));
+    
+    let (server_result, scheduler_result) = tokio::join!(server_task, scheduler_task);
+    server_result?;
+    scheduler_result?;
+    
+    info!("✅ 组合模式已停止");
+    Ok(())
+}
+
+fn history_format_arg() -> Arg {
+    Arg::new("format")
+        .long("format")
+        .value_name("FORMAT")
+        .value_parser(["table", "json"])
+        .default_value("table")
+        .help("输出格式")
+}
+
+/// 获取跨进程的生成锁，已有任务在运行时返回错误
+///
+/// 修改历史记录的命令都要先持有该锁再加载历史记录，避免覆盖其他进程的提交。
+fn acquire_run_lock() -> Result<RunLock> {
+    RunLock::try_acquire()?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "已有其他日报生成任务正在运行（{}），请稍后再试",
+            RunLock::holder().unwrap_or_default()
+        )
+    })
+}
+
+/// 执行 `history` 子命令
+fn run_history_command(matches: &clap::ArgMatches) -> Result<()> {
+    // forget 会修改历史记录，需要与生成任务互斥
+    let _run_lock = match matches.subcommand_name() {
+        Some("forget") => Some(acquire_run_lock()?),
+        _ => None,
+    };
+    let mut history_manager = HistoryManager::new()?;
+    
+    match matches.subcommand() {
+        Some(("search", sub)) => {
+            let query = sub.get_one::<String>("query").unwrap();
+            let filter = HistoryFilter {
+                source: sub.get_one::<String>("source").cloned(),
+                since: sub.get_one::<String>("since")
+                    .map(|date| parse_date_arg(date, 0))
+                    .transpose()?,
+                until: sub.get_one::<String>("until")
+                    .map(|date| parse_date_arg(date, 1))
+                    .transpose()?,
+            };
+            
+            let items = history_manager.search_filtered(query, &filter);
+            if sub.get_one::<String>("format").map(String::as_str) == Some("json") {
+                println!("{}", serde_json::to_string_pretty(&items)?);
+            } else if items.is_empty() {
+                println!("没有找到匹配 \"{}\" 的历史记录", query);
+            } else {
+                history::print_items_table(&items);
+            }
+        }
+        Some(("show", sub)) => {
+            let id = sub.get_one::<String>("id").unwrap();
+            let Some(item) = history_manager.get(id) else {
+                return Err(anyhow::anyhow!("未找到 ID 为 {} 的历史记录", id));
+            };
+            
+            if sub.get_one::<String>("format").map(String::as_str) == Some("json") {
+                println!("{}", serde_json::to_string_pretty(item)?);
+            } else {
+                item.display();
+            }
+        }
+        Some(("reports", sub)) => {
+            let url = sub.get_one::<String>("url").unwrap();
+            let reports = history_manager.reports_for_url(url);
+            
+            if sub.get_one::<String>("format").map(String::as_str) == Some("json") {
+                println!("{}", serde_json::to_string_pretty(&reports)?);
+            } else if reports.is_empty() {
+                println!("没有日报收录过 {}", url);
+            } else {
+                println!("📰 收录过该新闻的日报:");
+                for report in reports {
+                    println!("  - {} ({}, {})", report.id, report.file, report.date.format("%Y-%m-%d %H:%M:%S UTC"));
+                }
+            }
+        }
+        Some(("forget", sub)) => {
+            let key = sub.get_one::<String>("key").unwrap();
+            let removed_count = history_manager.forget(key)?;
+            if removed_count == 0 {
+                return Err(anyhow::anyhow!("未找到匹配 {} 的历史记录", key));
+            }
+            println!("已取消 {} 条记录的已处理标记，下次运行时将重新处理", removed_count);
+        }
+        Some(("export", sub)) => {
+            let items: Vec<_> = history_manager.items().iter().collect();
+            let content = match sub.get_one::<String>("format").map(String::as_str) {
+                Some("csv") => history::items_to_csv(&items),
+                _ => format!("{}\n", serde_json::to_string_pretty(&items)?),
+            };
+            
+            if let Some(output_path) = sub.get_one::<String>("output") {
+                std::fs::write(output_path, content)?;
+                info!("已导出 {} 条历史记录到: {}", items.len(), output_path);
+            } else {
+                print!("{}", content);
+            }
+        }
+        _ => unreachable!("clap 保证必须提供子命令"),
+    }
+    
+    Ok(())
+}
+
+/// 解析 `YYYY-MM-DD` 格式的日期参数，`offset_days` 用于把截止日期扩展到当天结束
+fn parse_date_arg(value: &str, offset_days: i64) -> Result<DateTime<Utc>> {
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|e| anyhow::anyhow!("无效的日期 {}: {} (格式应为 YYYY-MM-DD)", value, e))?;
+    let start = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    Ok(start + chrono::Duration::days(offset_days))
+}
+
+/// 检查配置文件，`probe` 为 true 时请求每个 RSS 源并报告检测到的格式，返回是否没有问题
+async fn run_check_config(probe: bool) -> bool {
+    let path = match Config::find_file() {
+        Ok(path) => path,
+        Err(e) => {
+            println!("❌ {}", e);
+            return false;
+        }
+    };
+    
+    let mut config = match &path {
+        Some(path) => {
+            println!("🔍 检查配置文件: {}", path.display());
+            match Config::parse_file(path) {
+                Ok(config) => config,
+                Err(e) => {
+                    println!("❌ {}", e);
+                    return false;
+                }
+            }
+        }
+        None => {
+            println!("⚠️ 配置文件不存在，将使用默认配置");
+            Config::default()
+        }
+    };
+    if config.is_legacy().unwrap_or(false) {
+        println!("💡 这是早期 {} 的配置格式，建议迁移到 {}", config::LEGACY_CONFIG_FILE, config::CONFIG_FILE);
+    }
+    
+    let mut problems = config.validate();
+    if let Err(e) = scheduler::parse_schedules(&config) {
+        problems.push(e.to_string());
+    }
+    if let Err(e) = config.apply_env() {
+        problems.push(e.to_string());
+    }
+    if problems.is_empty() {
+        println!(
+            "✅ 配置有效：{} 个 RSS 源，{} 个定时任务",
+            config.sources.len(),
+            config.effective_schedules().len()
+        );
+    } else {
+        for problem in &problems {
+            println!("❌ {}", problem);
+        }
+    }
+    
+    let mut ok = problems.is_empty();
+    if probe {
+        println!();
+        println!("📡 探测 RSS 源:");
+        let fetcher = RssFetcher::new();
+        for source in &config.sources {
+            if config::validate_source_url(&source.url).is_err() {
+                continue;
+            }
+            match fetcher.probe(&source.url).await {
+                Ok(result) => match result.parsed {
+                    Ok(items) => println!(
+                        "  ✅ {} — {}，{} 个条目（{} ms）",
+                        source.name, result.format, items, result.elapsed.as_millis()
+                    ),
+                    Err(e) => {
+                        ok = false;
+                        println!("  ❌ {} — 检测到 {}，但无法解析: {}", source.name, result.format, e);
+                    }
+                },
+                Err(e) => {
+                    ok = false;
+                    println!("  ❌ {} — 请求失败: {}", source.name, e);
+                }
+            }
+        }
+    }
+    
+    ok
+}
+
 fn format_daily_report(report: &DailyReport) -> String {
     let mut output = String::new();
     
     // 使用 rustcc.cn 日报的标题格式
-    output.push_str(&format!("【Rust日报】{} \n\n", report.date.format("%Y-%m-%d")));
+    output.push_str(&format!("{} \n\n", report.title()));
     
     // 直接输出 AI 生成的摘要内容
     output.push_str(&report.summary);
@@ -445,9 +902,31 @@ fn format_daily_report(report: &DailyReport) -> String {
         output.push_str("\n\n--\n\nFrom 日报小组 Rust Daily\n");
     }
     
+    // 附加生成时的提示
+    if !report.notices.is_empty() {
+        output.push_str("\n\n");
+        for notice in &report.notices {
+            output.push_str(&format!("> ⚠️ {}\n", notice));
+        }
+    }
+    
     // 添加生成信息
     output.push_str(&format!("\n\n*Generated at {} by Rust Daily*\n", 
-        Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
+        report.date.format("%Y-%m-%d %H:%M:%S UTC")));
     
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::NewsItem;
+
+    /// 标识作为 feed GUID 发布，不能随实现或 Rust 版本变化
+    #[test]
+    fn news_item_id_is_stable() {
+        assert_eq!(
+            NewsItem::id_for("https://blog.rust-lang.org/", "Announcing Rust"),
+            "156b0870acb9fd56"
+        );
+    }
+}
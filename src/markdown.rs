@@ -0,0 +1,78 @@
+use pulldown_cmark::{html, Options, Parser};
+
+/// 将 CommonMark 渲染为经过清理的 HTML
+///
+/// AI 生成的摘要和 RSS 源内容都不可信，渲染结果会经过 ammonia 过滤，
+/// 只保留安全的标签和属性，链接自动加上 `rel="noopener noreferrer"`。
+pub fn render_markdown(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let parser = Parser::new_ext(markdown, options);
+    let mut unsafe_html = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, parser);
+
+    ammonia::clean(&unsafe_html)
+}
+
+/// 清理 RSS 源提供的 HTML 片段，保留安全的排版标签，过滤规则与 [`render_markdown`] 相同
+pub fn sanitize_html(html: &str) -> String {
+    ammonia::clean(html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_markdown, sanitize_html};
+
+    /// 设置 `BLESS=1` 运行测试可重新生成期望的 HTML
+    #[test]
+    fn sample_output_matches_golden() {
+        let golden_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/sample-output.html");
+        let rendered = render_markdown(include_str!("../sample-output.md"));
+        if std::env::var_os("BLESS").is_some() {
+            std::fs::write(golden_path, &rendered).unwrap();
+        }
+        let expected = std::fs::read_to_string(golden_path).unwrap();
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn setext_heading_with_dashes() {
+        let html = render_markdown("Eon：简单且友好的配置格式\n----\n");
+        assert_eq!(html.trim(), "<h2>Eon：简单且友好的配置格式</h2>");
+    }
+
+    #[test]
+    fn links_and_emphasis() {
+        let html = render_markdown("**粗体** *斜体* [链接](https://example.com/a)");
+        assert!(html.contains("<strong>粗体</strong>"));
+        assert!(html.contains("<em>斜体</em>"));
+        assert!(html.contains(r#"<a href="https://example.com/a" rel="noopener noreferrer">链接</a>"#));
+    }
+
+    #[test]
+    fn fenced_code_block() {
+        let html = render_markdown("```rust\nfn main() { println!(\"<hi>\"); }\n```\n");
+        assert!(html.contains("<pre><code"));
+        assert!(html.contains("fn main() { println!(\"&lt;hi&gt;\"); }"), "{}", html);
+    }
+
+    #[test]
+    fn strips_script_and_event_handlers() {
+        let html = render_markdown("前<script>alert(1)</script>后\n\n<img src=\"x.png\" onerror=\"alert(1)\">");
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("alert(1)"));
+        assert!(!html.contains("onerror"));
+        assert!(html.contains("前"));
+    }
+
+    #[test]
+    fn sanitize_keeps_markup_and_strips_scripts() {
+        assert_eq!(sanitize_html("<p>Hello <b>world</b></p>"), "<p>Hello <b>world</b></p>");
+        assert_eq!(sanitize_html("a < b & c"), "a &lt; b &amp; c");
+        let html = sanitize_html("<p onclick=\"x()\">hi<script>alert(1)</script><a href=\"javascript:alert(1)\">x</a></p>");
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("alert(1)"));
+    }
+}
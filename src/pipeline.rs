@@ -0,0 +1,246 @@
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::history::HistoryManager;
+use crate::report_store::ReportStore;
+use crate::source_status::SourceStatusStore;
+use crate::telemetry;
+use crate::{DailyGenerator, DailyReport, NewsItem, RssFetcher};
+
+/// 一次日报生成任务的选项，命令行、定时任务和管理 API 共用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunOptions {
+    /// 忽略历史记录，处理所有新闻，且不把新闻标记为已处理
+    #[serde(default)]
+    pub force: bool,
+    /// 使用批量处理模式（默认逐条生成摘要）
+    #[serde(default)]
+    pub batch: bool,
+    /// 获取最近几天的新闻
+    #[serde(default = "default_days")]
+    pub days: u64,
+}
+
+fn default_days() -> u64 {
+    1
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            force: false,
+            batch: false,
+            days: default_days(),
+        }
+    }
+}
+
+/// 生成任务的结果
+#[derive(Debug)]
+pub enum RunOutcome {
+    /// 所有 RSS 源都没有返回新闻
+    NoNews,
+    /// 获取到的新闻都已处理过
+    NothingNew { fetched: usize },
+    /// 生成并保存了新日报，`filtered` 为去重后交给 AI 处理的新闻数
+    Generated {
+        report: DailyReport,
+        fetched: usize,
+        filtered: usize,
+    },
+}
+
+/// 从配置的所有 RSS 源获取新闻，单个源失败只记录警告
+///
+/// 每个源的成功和失败都会记录到源状态中，供健康检查使用。连续失败达到
+/// `fetch.quarantine_after` 次的源会被暂停获取，直到重试成功。
+/// 返回获取到的新闻，以及需要写入日报的提示（被暂停的源）。
+pub async fn fetch_news(config: &Config, days: u64) -> (Vec<NewsItem>, Vec<String>) {
+    let rss_fetcher = RssFetcher::new();
+    let policy = &config.fetch;
+    let now = Utc::now();
+    let mut all_news = Vec::new();
+    let mut statuses = SourceStatusStore::load()
+        .map_err(|e| warn!("加载 RSS 源状态失败: {}", e))
+        .ok();
+    
+    info!("正在获取 RSS 源...");
+    for rss_url in &config.sources {
+        if rss_url.name.trim().is_empty() || rss_url.url.trim().is_empty() {
+            warn!("跳过名称或 URL 为空的 RSS 源，请检查配置文件");
+            continue;
+        }
+        if let Some(statuses) = &statuses {
+            if !statuses.should_fetch(rss_url, policy, now) {
+                info!("RSS 源 {} 已暂停获取，跳过", rss_url.name);
+                continue;
+            }
+        }
+        
+        let validators = statuses
+            .as_ref()
+            .map(|statuses| statuses.validators(rss_url))
+            .unwrap_or_default();
+        match rss_fetcher.fetch_source(rss_url, days, &validators).await {
+            Ok(feed) => {
+                if feed.not_modified {
+                    info!("{} 自上次获取以来没有更新", rss_url.name);
+                } else {
+                    info!("从 {} 获取到 {} 条新闻", rss_url.name, feed.items.len());
+                }
+                if let Some(statuses) = &mut statuses {
+                    if statuses.record_success(rss_url, &feed) {
+                        info!("RSS 源 {} 重试成功，已恢复获取", rss_url.name);
+                    }
+                }
+                all_news.extend(feed.items);
+            }
+            Err(e) => {
+                warn!("获取 RSS 源 {} 失败: {}", rss_url.name, e);
+                if let Some(statuses) = &mut statuses {
+                    if statuses.record_failure(rss_url, &e.to_string(), policy) {
+                        warn!(
+                            "RSS 源 {} 连续失败 {} 次，已暂停获取，每 {} 小时重试一次",
+                            rss_url.name, policy.quarantine_after, policy.retry_after_hours
+                        );
+                    }
+                }
+            }
+        }
+    }
+    
+    let Some(statuses) = statuses else {
+        return (all_news, Vec::new());
+    };
+    if let Err(e) = statuses.save() {
+        warn!("保存 RSS 源状态失败: {}", e);
+    }
+    
+    let notices = config
+        .sources
+        .iter()
+        .filter_map(|source| {
+            let status = statuses.current(source)?;
+            status.quarantined_at.map(|since| {
+                format!(
+                    "RSS 源「{}」自 {} 起连续获取失败 {} 次，已暂停获取",
+                    source.name,
+                    since.format("%Y-%m-%d"),
+                    status.consecutive_failures
+                )
+            })
+        })
+        .collect();
+    
+    (all_news, notices)
+}
+
+/// 获取新闻、生成日报并保存
+///
+/// 非强制模式下过滤已处理的新闻，并通过两阶段提交在日报写入成功后才标记为已处理。
+pub async fn run(
+    config: &Config,
+    options: &RunOptions,
+    store: &ReportStore,
+    history_manager: &mut HistoryManager,
+) -> Result<RunOutcome> {
+    let started = Instant::now();
+    let result = generate(config, options, store, history_manager).await;
+    let outcome = match &result {
+        Ok(RunOutcome::NoNews) => "no_news",
+        Ok(RunOutcome::NothingNew { .. }) => "nothing_new",
+        Ok(RunOutcome::Generated { .. }) => "generated",
+        Err(_) => "error",
+    };
+    telemetry::record_generation(outcome, started.elapsed());
+    result
+}
+
+async fn generate(
+    config: &Config,
+    options: &RunOptions,
+    store: &ReportStore,
+    history_manager: &mut HistoryManager,
+) -> Result<RunOutcome> {
+    let (all_news, notices) = fetch_news(config, options.days).await;
+    if all_news.is_empty() {
+        return Ok(RunOutcome::NoNews);
+    }
+    
+    let fetched = all_news.len();
+    info!("总共获取到 {} 条新闻", fetched);
+    
+    let filtered_news = if options.force {
+        all_news
+    } else {
+        history_manager.filter_unprocessed(all_news)
+    };
+    telemetry::record_items(fetched, filtered_news.len());
+    
+    if filtered_news.is_empty() {
+        return Ok(RunOutcome::NothingNew { fetched });
+    }
+    
+    let filtered = filtered_news.len();
+    let daily_generator = DailyGenerator::from_config(&config.llm)?;
+    
+    info!("正在生成日报摘要，处理 {} 条新闻...", filtered_news.len());
+    if options.batch {
+        info!("使用批量处理模式，生成整体摘要");
+    } else {
+        info!("使用单条处理模式，生成详细摘要");
+    }
+    let mut report = daily_generator.generate_daily_report(filtered_news, !options.batch).await?;
+    report.notices = notices;
+    
+    let report_ref = store.assign_id(&mut report)?;
+    if options.force {
+        store.save(&report, &report_ref.file)?;
+    } else {
+        store.save_and_commit(&report, &report_ref, history_manager)?;
+    }
+    
+    Ok(RunOutcome::Generated { report, fetched, filtered })
+}
+
+/// 用已保存日报中的新闻重新生成摘要，保留原有的 ID、期数和日期
+///
+/// `batch` 为 `None` 时沿用原日报的处理模式（新闻带 AI 摘要即为单条模式）。
+/// 新的单条摘要会同步到历史记录中对应的新闻。日报不存在时返回 `None`。
+pub async fn regenerate(
+    config: &Config,
+    store: &ReportStore,
+    id: &str,
+    batch: Option<bool>,
+) -> Result<Option<DailyReport>> {
+    if !store.report_ids()?.iter().any(|known| known == id) {
+        return Ok(None);
+    }
+    let Some(original) = store.load(id)? else {
+        return Ok(None);
+    };
+    
+    let batch = batch.unwrap_or_else(|| original.items.iter().all(|item| item.ai_summary.is_none()));
+    let items: Vec<NewsItem> = original.items
+        .iter()
+        .cloned()
+        .map(|item| NewsItem { ai_summary: None, ..item })
+        .collect();
+    
+    info!("正在重新生成日报 {}，共 {} 条新闻", id, items.len());
+    let daily_generator = DailyGenerator::from_config(&config.llm)?;
+    let mut report = daily_generator.generate_daily_report(items, !batch).await?;
+    report.id = original.id;
+    report.edition = original.edition;
+    report.date = original.date;
+    report.notices = original.notices;
+    
+    let report_ref = store.assign_id(&mut report)?;
+    let mut history_manager = HistoryManager::new()?;
+    store.save_and_commit(&report, &report_ref, &mut history_manager)?;
+    Ok(Some(report))
+}
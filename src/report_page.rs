@@ -0,0 +1,165 @@
+use crate::rss_server::RssServerConfig;
+use crate::DailyReport;
+
+/// 日报页面的前后导航
+#[derive(Debug, Default)]
+pub struct ReportNav {
+    /// 更早的一期
+    pub prev: Option<String>,
+    /// 更新的一期
+    pub next: Option<String>,
+}
+
+/// 渲染单期日报的 HTML 页面，`summary_html` 为已转换好的摘要内容
+pub fn render_report_page(
+    config: &RssServerConfig,
+    report: &DailyReport,
+    summary_html: &str,
+    nav: &ReportNav,
+) -> String {
+    let title = escape_html(&report.title());
+    let base_url = escape_html(&config.base_url);
+    let id = escape_html(&report.id);
+
+    let mut body = String::new();
+    body.push_str(&format!("<h1>{}</h1>\n", title));
+    body.push_str(&format!(
+        "<p class=\"meta\">生成时间: {} · {} 条技术资讯 · <a href=\"{}/reports/{}.md\">Markdown</a> · <a href=\"{}/reports/{}.json\">JSON</a></p>\n",
+        report.date.format("%Y-%m-%d %H:%M:%S UTC"),
+        report.items.len(),
+        base_url, id, base_url, id
+    ));
+    for notice in &report.notices {
+        body.push_str(&format!("<p class=\"meta\">⚠️ {}</p>\n", escape_html(notice)));
+    }
+    body.push_str(&render_nav(&base_url, nav));
+
+    body.push_str("<article>\n");
+    body.push_str(summary_html);
+    body.push_str("\n</article>\n");
+
+    if !report.items.is_empty() {
+        body.push_str("<h2>🔗 相关链接</h2>\n<ul>\n");
+        for item in &report.items {
+            body.push_str(&format!(
+                "<li>{} - <em>{}</em></li>\n",
+                render_external_link(&item.link, &item.title),
+                escape_html(&item.source)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    body.push_str(&render_nav(&base_url, nav));
+
+    render_layout(config, &title, &body)
+}
+
+/// 渲染 404 页面
+pub fn render_not_found(config: &RssServerConfig, key: &str) -> String {
+    let body = format!(
+        "<h1>未找到日报</h1>\n<p>没有找到 <code>{}</code> 对应的日报。</p>\n<p><a href=\"{}/feed\">订阅 RSS</a></p>\n",
+        escape_html(key),
+        escape_html(&config.base_url)
+    );
+    render_layout(config, "未找到日报", &body)
+}
+
+fn render_nav(base_url: &str, nav: &ReportNav) -> String {
+    let mut html = String::from("<nav>");
+    if let Some(prev) = &nav.prev {
+        html.push_str(&format!("<a href=\"{}/reports/{}\">← 上一期</a>", base_url, escape_html(prev)));
+    }
+    if let Some(next) = &nav.next {
+        html.push_str(&format!("<a class=\"next\" href=\"{}/reports/{}\">下一期 →</a>", base_url, escape_html(next)));
+    }
+    html.push_str("</nav>\n");
+    html
+}
+
+fn render_layout(config: &RssServerConfig, title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="{language}">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title} - {site}</title>
+<link rel="alternate" type="application/rss+xml" title="{site} (RSS)" href="{base_url}/feed">
+<link rel="alternate" type="application/atom+xml" title="{site} (Atom)" href="{base_url}/atom.xml">
+<link rel="alternate" type="application/feed+json" title="{site} (JSON Feed)" href="{base_url}/feed.json">
+<link rel="alternate" type="application/rss+xml" title="{site} 单条新闻 (RSS)" href="{base_url}/feed/items">
+<style>
+body {{ max-width: 46em; margin: 2em auto; padding: 0 1em; font-family: -apple-system, "PingFang SC", "Microsoft YaHei", sans-serif; line-height: 1.7; color: #222; }}
+.meta {{ color: #666; font-size: 0.9em; }}
+nav {{ display: flex; justify-content: space-between; margin: 1.5em 0; }}
+nav .next {{ margin-left: auto; }}
+pre {{ overflow-x: auto; background: #f6f8fa; padding: 0.8em; }}
+code {{ background: #f6f8fa; padding: 0 0.2em; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        language = escape_html(&config.language),
+        title = title,
+        site = escape_html(&config.title),
+        base_url = escape_html(&config.base_url),
+        body = body
+    )
+}
+
+/// 渲染指向第三方地址的链接；只有 http/https 地址才生成 `href`，
+/// 其他协议（如 `javascript:`）只输出纯文本，避免在本站执行来源提供的脚本
+pub fn render_external_link(url: &str, text: &str) -> String {
+    match safe_external_url(url) {
+        Some(url) => format!(
+            "<a href=\"{}\" target=\"_blank\" rel=\"noopener\">{}</a>",
+            escape_html(&url),
+            escape_html(text)
+        ),
+        None => escape_html(text),
+    }
+}
+
+/// 第三方地址为 http/https 时返回规范化后的地址，其他协议返回 `None`
+pub fn safe_external_url(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()
+        .filter(|parsed| matches!(parsed.scheme(), "http" | "https"))
+        .map(String::from)
+}
+
+/// 转义 HTML 特殊字符
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_external_link;
+
+    #[test]
+    fn external_link_only_allows_http() {
+        assert_eq!(
+            render_external_link("https://example.com/a?b=1&c=2", "标题"),
+            "<a href=\"https://example.com/a?b=1&amp;c=2\" target=\"_blank\" rel=\"noopener\">标题</a>"
+        );
+        assert_eq!(render_external_link("javascript:alert(1)", "<x>"), "&lt;x&gt;");
+        assert_eq!(render_external_link("JavaScript:alert(1)", "x"), "x");
+        assert_eq!(render_external_link("data:text/html,hi", "x"), "x");
+        assert_eq!(render_external_link("/relative", "x"), "x");
+    }
+}
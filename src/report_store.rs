@@ -0,0 +1,255 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::history::{HistoryManager, ReportRef};
+use crate::DailyReport;
+
+/// 日报存储目录
+#[derive(Debug, Clone)]
+pub struct ReportStore {
+    dir: PathBuf,
+}
+
+impl ReportStore {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// 使用配置中的 `generation.reports_dir`
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.generation.reports_dir)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 日报文件的完整路径
+    pub fn path_for(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+
+    pub fn exists(&self, file: &str) -> bool {
+        self.path_for(file).exists()
+    }
+
+    /// 为日报分配 ID 和期数，返回对应的历史记录引用
+    ///
+    /// ID 格式为 `YYYY-MM-DD-NN`，NN 为当天的期数，同一天多次生成不会覆盖之前的日报。
+    /// 已有 ID 的日报（例如重新生成）保持原 ID 不变。
+    pub fn assign_id(&self, report: &mut DailyReport) -> Result<ReportRef> {
+        if report.id.is_empty() {
+            let date = report.date.format("%Y-%m-%d").to_string();
+            let existing = self.report_ids()?
+                .iter()
+                .filter(|id| id.starts_with(&date))
+                .count();
+
+            let mut edition = existing as u32 + 1;
+            while self.exists(&Self::file_name(&Self::make_id(&date, edition))) {
+                edition += 1;
+            }
+
+            report.id = Self::make_id(&date, edition);
+            report.edition = edition;
+        }
+
+        Ok(ReportRef {
+            id: report.id.clone(),
+            file: Self::file_name(&report.id),
+            date: report.date,
+        })
+    }
+
+    fn make_id(date: &str, edition: u32) -> String {
+        format!("{}-{:02}", date, edition)
+    }
+
+    /// 日报 ID 对应的文件名
+    pub fn file_name(id: &str) -> String {
+        format!("{}.json", id)
+    }
+
+    /// 列出所有日报 ID（即文件名去掉 `.json`），按从新到旧排序
+    pub fn report_ids(&self) -> Result<Vec<String>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut ids: Vec<String> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                    return None;
+                }
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(|stem| stem.to_string())
+            })
+            .collect();
+
+        // ID 以日期开头，按名称倒序即从新到旧
+        ids.sort_by(|a, b| b.cmp(a));
+        Ok(ids)
+    }
+
+    /// 计算报告目录的指纹，任何日报文件的增删改都会改变指纹
+    ///
+    /// 只读取文件元数据，不解析 JSON，用于判断缓存是否失效。
+    pub fn fingerprint(&self) -> Result<StoreFingerprint> {
+        use std::hash::{Hash, Hasher};
+        use std::collections::hash_map::DefaultHasher;
+
+        let mut hasher = DefaultHasher::new();
+        let mut last_modified: Option<SystemTime> = None;
+
+        if self.dir.exists() {
+            let mut entries: Vec<(String, u64, SystemTime)> = fs::read_dir(&self.dir)?
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| {
+                    entry.path().extension().and_then(|ext| ext.to_str()) == Some("json")
+                })
+                .filter_map(|entry| {
+                    let metadata = entry.metadata().ok()?;
+                    let modified = metadata.modified().ok()?;
+                    Some((entry.file_name().to_string_lossy().into_owned(), metadata.len(), modified))
+                })
+                .collect();
+            entries.sort();
+
+            for (name, len, modified) in &entries {
+                name.hash(&mut hasher);
+                len.hash(&mut hasher);
+                modified.hash(&mut hasher);
+            }
+            last_modified = entries.iter().map(|(_, _, modified)| *modified).max();
+        }
+
+        Ok(StoreFingerprint {
+            hash: hasher.finish(),
+            last_modified: last_modified.map(DateTime::<Utc>::from),
+        })
+    }
+
+    /// 按 ID 加载日报，不存在时返回 `None`
+    pub fn load(&self, id: &str) -> Result<Option<DailyReport>> {
+        let path = self.path_for(&Self::file_name(id));
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let mut report: DailyReport = serde_json::from_str(&content)?;
+
+        // 旧版本保存的日报没有 ID，使用文件名作为 ID
+        if report.id.is_empty() {
+            report.id = id.to_string();
+        }
+        if report.edition == 0 {
+            report.edition = 1;
+        }
+
+        Ok(Some(report))
+    }
+
+    /// 删除日报文件，不存在时返回 `false`
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        // 只接受目录中已有的 ID，避免请求参数构造出目录外的路径
+        if !self.report_ids()?.iter().any(|known| known == id) {
+            return Ok(false);
+        }
+        
+        let path = self.path_for(&Self::file_name(id));        
+        fs::remove_file(&path)?;
+        info!("已删除日报: {}", path.display());
+        Ok(true)
+    }
+
+    /// 加载最新的若干份日报，无法解析的文件会被跳过
+    pub fn list(&self, limit: usize) -> Result<Vec<DailyReport>> {
+        let mut reports = Vec::new();
+
+        for id in self.report_ids()?.into_iter().take(limit) {
+            match self.load(&id) {
+                Ok(Some(report)) => reports.push(report),
+                Ok(None) => {}
+                Err(e) => warn!("解析报告文件失败 {}: {}", id, e),
+            }
+        }
+
+        Ok(reports)
+    }
+
+    /// 原子写入日报：先写临时文件再重命名，读取方不会看到写了一半的 JSON
+    pub fn save(&self, report: &DailyReport, file: &str) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+
+        let path = self.path_for(file);
+        let report_json = serde_json::to_string_pretty(report)?;
+        write_atomic(&path, report_json.as_bytes())?;
+
+        info!("报告已保存到: {}", path.display());
+        Ok(path)
+    }
+
+    /// 两阶段保存日报并更新历史记录
+    ///
+    /// 1. 在历史记录中把新闻登记为待提交状态
+    /// 2. 原子写入日报文件
+    /// 3. 提交任务，把新闻标记为已处理
+    ///
+    /// 第 2 步失败时放弃任务，新闻保持未处理状态；进程在第 2、3 步之间退出时，
+    /// 下次启动由 [`HistoryManager::recover_pending_runs`] 根据日报文件是否存在来决定提交或放弃。
+    pub fn save_and_commit(
+        &self,
+        report: &DailyReport,
+        report_ref: &ReportRef,
+        history_manager: &mut HistoryManager,
+    ) -> Result<PathBuf> {
+        let run_id = uuid::Uuid::new_v4().to_string();
+        history_manager.begin_run(&run_id, &report.items, report_ref)?;
+
+        let path = match self.save(report, &report_ref.file) {
+            Ok(path) => path,
+            Err(e) => {
+                if let Err(abort_err) = history_manager.abort_run(&run_id) {
+                    warn!("放弃任务 {} 失败: {}", run_id, abort_err);
+                }
+                return Err(e);
+            }
+        };
+
+        history_manager.commit_run(&run_id)?;
+        Ok(path)
+    }
+}
+
+/// 报告目录指纹
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreFingerprint {
+    pub hash: u64,
+    /// 最近一次修改日报文件的时间
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// 原子写文件：写入同目录下的临时文件后重命名
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow::anyhow!("无效的文件路径: {}", path.display()))?;
+    let tmp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    fs::write(&tmp_path, content)?;
+    if let Err(e) = fs::rename(&tmp_path, path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
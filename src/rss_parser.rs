@@ -2,10 +2,95 @@ use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc, Duration};
 use reqwest::Client;
 use rss::Channel;
+use std::fmt;
+use std::time::Instant;
 use tracing::{debug, warn};
 
+use crate::config::RssSource;
+use crate::telemetry;
 use crate::NewsItem;
 
+/// 根据根元素检测到的 feed 格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss2,
+    Rss1,
+    Atom,
+    Unknown,
+}
+
+impl FeedFormat {
+    /// 根据 XML 文档的根元素判断格式
+    pub fn detect(content: &str) -> Self {
+        let root = content
+            .match_indices('<')
+            .map(|(i, _)| &content[i + 1..])
+            .find(|tag| !tag.starts_with('?') && !tag.starts_with('!'))
+            .map(|tag| tag.split(|c: char| c.is_whitespace() || c == '>' || c == '/').next().unwrap_or(""));
+        match root {
+            Some("rss") => Self::Rss2,
+            Some("rdf:RDF") => Self::Rss1,
+            Some("feed") => Self::Atom,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for FeedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Rss2 => "RSS 2.0",
+            Self::Rss1 => "RSS 1.0",
+            Self::Atom => "Atom 1.0",
+            Self::Unknown => "未知格式",
+        })
+    }
+}
+
+/// 探测一个 RSS 源的结果
+#[derive(Debug)]
+pub struct FeedProbe {
+    pub format: FeedFormat,
+    /// 生成日报时使用的解析器能否读取该 feed，成功时为条目数
+    pub parsed: Result<usize, String>,
+    pub elapsed: std::time::Duration,
+}
+
+/// 获取一个 RSS 源的结果
+#[derive(Debug, Default)]
+pub struct FetchedFeed {
+    /// 指定天数内与 Rust 相关的新闻
+    pub items: Vec<NewsItem>,
+    /// feed 中全部条目的发布时间（过滤之前），用于统计源的更新情况
+    pub published: Vec<DateTime<Utc>>,
+    /// 响应中的缓存验证信息，下次获取时用于条件请求
+    pub validators: CacheValidators,
+    /// 服务器返回 304 Not Modified，自上次获取以来没有新内容
+    pub not_modified: bool,
+}
+
+/// 条件请求使用的缓存验证信息
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        }
+    }
+}
+
 pub struct RssFetcher {
     client: Client,
 }
@@ -17,32 +102,105 @@ impl RssFetcher {
         }
     }
 
-    pub async fn fetch_rss(&self, url: &str, days: u64) -> Result<Vec<NewsItem>> {
-        debug!("正在获取 RSS: {}", url);
+    /// 获取配置中的一个 RSS 源，并为新闻设置来源名称和源标签
+    ///
+    /// 带上次响应的 `validators` 发送条件请求，内容未变化时返回没有新闻的 [`FetchedFeed`]。
+    pub async fn fetch_source(&self, source: &RssSource, days: u64, validators: &CacheValidators) -> Result<FetchedFeed> {
+        let started = Instant::now();
+        let result = self.fetch_feed(&source.url, days, validators).await;
+        let outcome = match &result {
+            Ok(feed) if feed.not_modified => "not_modified",
+            Ok(_) => "success",
+            Err(_) => "failure",
+        };
+        telemetry::record_feed_fetch(&source.name, outcome, started.elapsed());
         
-        // 获取 RSS 内容
+        let mut feed = result?;
+        for item in &mut feed.items {
+            item.source = source.name.clone();
+            for tag in &source.tags {
+                if !item.tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                    item.tags.push(tag.clone());
+                }
+            }
+        }
+        Ok(feed)
+    }
+
+    /// 请求 feed 并检测格式，不过滤条目也不记录源状态，`--check-config --probe` 使用
+    pub async fn probe(&self, url: &str) -> Result<FeedProbe> {
+        let started = Instant::now();
         let response = self.client
             .get(url)
             .header("User-Agent", "Rust-Daily/1.0")
             .send()
             .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("HTTP 错误: {}", response.status()));
+        }
+        
+        let content = response.text().await?;
+        let parsed = Channel::read_from(content.as_bytes())
+            .map(|channel| channel.items.len())
+            .map_err(|e| e.to_string());
+        Ok(FeedProbe {
+            format: FeedFormat::detect(&content),
+            parsed,
+            elapsed: started.elapsed(),
+        })
+    }
+
+    /// 获取并解析 RSS，只保留指定天数内与 Rust 相关的条目
+    async fn fetch_feed(&self, url: &str, days: u64, validators: &CacheValidators) -> Result<FetchedFeed> {
+        debug!("正在获取 RSS: {}", url);
+        
+        // 获取 RSS 内容，带上次的验证信息发送条件请求
+        let mut request = self.client
+            .get(url)
+            .header("User-Agent", "Rust-Daily/1.0");
+        if let Some(etag) = &validators.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = request.send().await?;
         
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            debug!("RSS 未变化: {}", url);
+            return Ok(FetchedFeed {
+                validators: validators.clone(),
+                not_modified: true,
+                ..FetchedFeed::default()
+            });
+        }
         if !response.status().is_success() {
             return Err(anyhow!("HTTP 错误: {}", response.status()));
         }
         
+        let response_validators = CacheValidators::from_headers(response.headers());
         let content = response.text().await?;
         
         // 解析 RSS
         let channel = Channel::read_from(content.as_bytes())?;
         
         let cutoff_date = Utc::now() - Duration::days(days as i64);
-        let mut items = Vec::new();
+        let mut feed = FetchedFeed {
+            validators: response_validators,
+            ..FetchedFeed::default()
+        };
         
         for item in channel.items {
             let title = item.title.unwrap_or_default();
             let link = item.link.unwrap_or_default();
             let description = item.description.unwrap_or_default();
+            let mut tags: Vec<String> = Vec::new();
+            for category in &item.categories {
+                let name = category.name.trim();
+                if !name.is_empty() && !tags.iter().any(|tag| tag.eq_ignore_ascii_case(name)) {
+                    tags.push(name.to_string());
+                }
+            }
             
             // 解析发布日期
             let pub_date = if let Some(date_str) = item.pub_date {
@@ -65,6 +223,7 @@ impl RssFetcher {
             
             // 只包含指定天数内的新闻
             if let Some(date) = pub_date {
+                feed.published.push(date);
                 if date < cutoff_date {
                     continue;
                 }
@@ -72,17 +231,19 @@ impl RssFetcher {
             
             // 过滤 Rust 相关内容
             if Self::is_rust_related(&title) || Self::is_rust_related(&description) {
-                items.push(NewsItem {
+                feed.items.push(NewsItem {
                     title,
                     link,
                     description: Self::clean_html(&description),
                     pub_date,
                     source: String::new(), // 将由调用者设置
+                    ai_summary: None,
+                    tags,
                 });
             }
         }
         
-        Ok(items)
+        Ok(feed)
     }
 
     fn is_rust_related(text: &str) -> bool {
@@ -1,20 +1,33 @@
 use anyhow::Result;
 use axum::{
     extract::Query,
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
     routing::get,
     Router,
 };
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
 use rss::{ChannelBuilder, ItemBuilder};
 use serde::Deserialize;
-use std::{net::SocketAddr, path::Path};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
 use tokio::net::TcpListener;
 use tower::ServiceBuilder;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
-use crate::DailyReport;
+use crate::admin::{self, AdminState};
+use crate::api;
+use crate::config::{Config, ServerConfig};
+use crate::health;
+use crate::markdown;
+use crate::report_page::{self, escape_html, ReportNav};
+use crate::report_store::{ReportStore, StoreFingerprint};
+use crate::state::SharedState;
+use crate::telemetry;
+use crate::{DailyReport, NewsItem};
 
 /// RSS 服务器配置
 #[derive(Debug, Clone)]
@@ -25,6 +38,10 @@ pub struct RssServerConfig {
     pub title: String,
     pub description: String,
     pub language: String,
+    /// 设置后监听 Unix domain socket 而不是 TCP 端口（仅 Unix 平台）
+    pub unix_socket: Option<PathBuf>,
+    /// 管理 API 的访问令牌，未设置时不启用 `/admin` 路由
+    pub admin_token: Option<String>,
 }
 
 impl Default for RssServerConfig {
@@ -36,14 +53,56 @@ impl Default for RssServerConfig {
             title: "Rust Daily 技术日报".to_string(),
             description: "每日精选 Rust 技术新闻和资讯".to_string(),
             language: "zh-CN".to_string(),
+            unix_socket: None,
+            admin_token: None,
         }
     }
 }
 
 /// RSS 服务器
 pub struct RssServer {
-    config: RssServerConfig,
-    reports_dir: String,
+    /// 收到 SIGHUP 时可整体替换
+    config: Arc<RwLock<Arc<RssServerConfig>>>,
+    store: ReportStore,
+    cache: Arc<Mutex<FeedCache>>,
+    admin: Arc<AdminState>,
+    shared: SharedState,
+    /// 同一进程内是否运行着调度器，决定健康检查是否包含定时任务
+    local_scheduler: bool,
+}
+
+/// 已渲染的 feed
+#[derive(Debug, Clone)]
+struct CachedFeed {
+    body: String,
+    etag: String,
+    last_modified: Option<DateTime<Utc>>,
+}
+
+/// feed 渲染结果缓存，报告目录指纹变化时整体失效
+#[derive(Debug, Default)]
+struct FeedCache {
+    /// 报告目录指纹和共享状态中的日报版本
+    fingerprint: Option<(u64, u64)>,
+    /// 只缓存默认条数的 feed
+    feeds: HashMap<(FeedKind, FeedFormat), CachedFeed>,
+    reports: Option<Arc<Vec<DailyReport>>>,
+}
+
+impl FeedCache {
+    /// 下次访问时重新渲染
+    fn invalidate(&mut self) {
+        self.fingerprint = None;
+    }
+
+    /// 指纹变化时清空缓存
+    fn validate(&mut self, fingerprint: (u64, u64)) {
+        if self.fingerprint != Some(fingerprint) {
+            self.feeds.clear();
+            self.reports = None;
+            self.fingerprint = Some(fingerprint);
+        }
+    }
 }
 
 /// RSS feed 查询参数
@@ -56,23 +115,107 @@ struct RssFeedQuery {
 impl RssServer {
     pub fn new(config: RssServerConfig, reports_dir: String) -> Self {
         Self {
-            config,
-            reports_dir,
+            config: Arc::new(RwLock::new(Arc::new(config))),
+            store: ReportStore::new(reports_dir),
+            cache: Arc::new(Mutex::new(FeedCache::default())),
+            admin: Arc::new(AdminState::default()),
+            shared: SharedState::default(),
+            local_scheduler: false,
         }
     }
 
-    /// 启动 RSS 服务器
+    /// 与同一进程内的调度器共享生成锁和缓存失效通知
+    pub fn with_shared_state(mut self, shared: SharedState) -> Self {
+        self.shared = shared;
+        self
+    }
+
+    /// 标记同一进程内运行着调度器（组合模式）
+    pub fn with_local_scheduler(mut self) -> Self {
+        self.local_scheduler = true;
+        self
+    }
+
+    pub(crate) fn shared(&self) -> &SharedState {
+        &self.shared
+    }
+
+    /// 缓存使用的指纹：报告目录指纹加上共享状态中的日报版本
+    fn cache_fingerprint(&self) -> Result<(StoreFingerprint, (u64, u64))> {
+        let fingerprint = self.store.fingerprint()?;
+        let key = (fingerprint.hash, self.shared.revision());
+        Ok((fingerprint, key))
+    }
+
+    pub(crate) fn config(&self) -> Arc<RssServerConfig> {
+        self.config.read().unwrap().clone()
+    }
+
+    /// 重新加载配置
+    ///
+    /// 监听地址和是否启用管理 API 在启动时确定，变化时保留原值并提示需要重启。
+    pub fn reload_config(&self, mut config: RssServerConfig) {
+        let current = self.config();
+        if config.host != current.host || config.port != current.port || config.unix_socket != current.unix_socket {
+            warn!("监听地址的修改需要重启服务器才能生效");
+            config.host = current.host.clone();
+            config.port = current.port;
+            config.unix_socket = current.unix_socket.clone();
+        }
+        if config.admin_token.is_some() != current.admin_token.is_some() {
+            warn!("启用或停用管理 API 需要重启服务器才能生效");
+            config.admin_token = current.admin_token.clone();
+        }
+
+        *self.config.write().unwrap() = Arc::new(config);
+        // 标题、描述等会写入 feed，清空已渲染的缓存
+        self.cache.lock().unwrap().invalidate();
+        info!("✅ RSS 服务器配置已重新加载");
+    }
+
+    pub(crate) fn store(&self) -> &ReportStore {
+        &self.store
+    }
+
+    pub(crate) fn admin(&self) -> &AdminState {
+        &self.admin
+    }
+
+    /// 启动 RSS 服务器，收到 Ctrl+C 或 SIGTERM 后处理完进行中的请求再退出
     pub async fn start(&self) -> Result<()> {
+        self.start_with_shutdown(shutdown_signal()).await
+    }
+
+    /// 启动 RSS 服务器，`shutdown` 完成后停止接受新连接并等待进行中的请求结束
+    pub async fn start_with_shutdown<F>(&self, shutdown: F) -> Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        telemetry::install();
         let app = self.create_router();
-        let addr = SocketAddr::from(([127, 0, 0, 1], self.config.port));
         
-        info!("🚀 RSS 服务器启动在 http://{}:{}", self.config.host, self.config.port);
-        info!("📡 RSS Feed URL: {}/feed", self.config.base_url);
-        info!("📄 API 端点: {}/api/reports", self.config.base_url);
-
+        #[cfg(unix)]
+        if let Some(socket_path) = &self.config().unix_socket {
+            info!("🚀 RSS 服务器监听 Unix socket: {}", socket_path.display());
+            info!("📡 RSS Feed URL: {}/feed", self.config().base_url);
+            return serve_unix(socket_path, app, shutdown).await;
+        }
+        
+        let addr = self.config().bind_addr().await?;
         let listener = TcpListener::bind(addr).await?;
-        axum::serve(listener, app).await?;
         
+        info!("🚀 RSS 服务器启动在 http://{}", listener.local_addr()?);
+        info!("📡 RSS Feed URL: {}/feed", self.config().base_url);
+        info!("📄 API 端点: {}/api/reports", self.config().base_url);
+        if self.config().admin_token.is_some() {
+            info!("🛠️ 管理 API 已启用: {}/admin", self.config().base_url);
+        }
+
+        axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown)
+            .await?;
+        
+        info!("✅ RSS 服务器已停止");
         Ok(())
     }
 
@@ -80,157 +223,427 @@ impl RssServer {
     fn create_router(&self) -> Router {
         let server = self.clone();
         
-        Router::new()
+        let mut router = Router::new()
             .route("/", get(|| async { "Rust Daily RSS Server" }))
             .route("/feed", get(rss_feed_handler))
             .route("/feed.xml", get(rss_feed_handler))
             .route("/rss", get(rss_feed_handler))
-            .route("/api/reports", get(api_reports_handler))
-            .route("/health", get(|| async { "OK" }))
+            .route("/atom.xml", get(atom_feed_handler))
+            .route("/feed.json", get(json_feed_handler))
+            .route("/feed/items", get(items_feed_handler))
+            .route("/feed/source/:name", get(source_feed_handler))
+            .route("/feed/tag/:tag", get(tag_feed_handler))
+            .route("/reports/:key", get(report_page_handler))
+            .route("/api/reports", get(api::list_reports_handler))
+            .route("/api/reports/:id", get(api::report_detail_handler))
+            .route("/health", get(health_handler))
+            .route("/metrics", get(telemetry::metrics_handler));
+        
+        if self.config().admin_token.is_some() {
+            router = router.merge(admin::router(server.clone()));
+        }
+        
+        router
             .with_state(server)
-            .layer(ServiceBuilder::new())
+            .layer(ServiceBuilder::new().layer(axum::middleware::from_fn(track_http_metrics)))
     }
 
-    /// 加载历史报告
-    fn load_reports(&self, limit: Option<usize>) -> Result<Vec<DailyReport>> {
-        let reports_dir = Path::new(&self.reports_dir);
+    /// 获取渲染好的 feed，报告目录未变化时直接使用缓存
+    ///
+    /// 来源或标签在已保存的日报中不存在时返回 `None`。
+    fn cached_feed(&self, kind: FeedKind, format: FeedFormat, limit: Option<usize>) -> Result<Option<CachedFeed>> {
+        let limit = kind.limit(limit);
+        let default_limit = limit == kind.limit(None);
+        let (fingerprint, cache_key) = self.cache_fingerprint()?;
+        // 来源和标签不区分大小写，缓存键统一为小写
+        let key = (kind.normalized(), format);
         
-        if !reports_dir.exists() {
-            return Ok(vec![]);
+        if default_limit {
+            let mut cache = self.cache.lock().unwrap();
+            cache.validate(cache_key);
+            if let Some(feed) = cache.feeds.get(&key) {
+                return Ok(Some(feed.clone()));
+            }
         }
+        
+        let (kind, entries): (FeedKind, Vec<FeedEntry>) = match kind {
+            FeedKind::Reports => {
+                let reports = self.store.list(limit)?;
+                (kind, reports.iter().map(|report| self.report_entry(report)).collect())
+            }
+            _ => {
+                let reports = self.all_reports()?;
+                // feed 标题等使用日报中实际的来源或标签名称，而不是请求路径中的写法
+                let Some(kind) = kind.resolve(&reports) else {
+                    return Ok(None);
+                };
+                let entries = self.item_entries(&reports, &kind, limit);
+                (kind, entries)
+            }
+        };
+        let meta = self.feed_meta(kind);
+        let body = match format {
+            FeedFormat::Rss => self.generate_rss(&meta, entries)?,
+            FeedFormat::Atom => self.generate_atom(&meta, entries)?,
+            FeedFormat::Json => self.generate_json_feed(&meta, entries)?,
+        };
+        
+        let feed = CachedFeed {
+            etag: content_etag(&body),
+            body,
+            last_modified: fingerprint.last_modified,
+        };
+        
+        // 条数来自请求，只缓存默认条数的 feed，避免缓存无限增长
+        let mut cache = self.cache.lock().unwrap();
+        if default_limit && cache.fingerprint == Some(cache_key) {
+            cache.feeds.insert(key, feed.clone());
+        }
+        
+        Ok(Some(feed))
+    }
 
-        let mut reports = Vec::new();
-        let mut entries: Vec<_> = std::fs::read_dir(reports_dir)?
-            .filter_map(|entry| entry.ok())
-            .filter(|entry| {
-                entry.path().extension()
-                    .and_then(|ext| ext.to_str())
-                    .map(|ext| ext == "json")
-                    .unwrap_or(false)
-            })
-            .collect();
+    /// 加载全部日报（从新到旧），报告目录未变化时直接使用缓存
+    pub(crate) fn all_reports(&self) -> Result<Arc<Vec<DailyReport>>> {
+        let (_, cache_key) = self.cache_fingerprint()?;
+        
+        {
+            let mut cache = self.cache.lock().unwrap();
+            cache.validate(cache_key);
+            if let Some(reports) = &cache.reports {
+                return Ok(reports.clone());
+            }
+        }
+        
+        let reports = Arc::new(self.store.list(usize::MAX)?);
+        
+        let mut cache = self.cache.lock().unwrap();
+        if cache.fingerprint == Some(cache_key) {
+            cache.reports = Some(reports.clone());
+        }
+        
+        Ok(reports)
+    }
 
-        // 按文件名排序（日期）
-        entries.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    /// 按 ID 或日期查找日报，日期对应当天最新一期；同时返回前后导航
+    pub(crate) fn find_report(&self, key: &str) -> Result<Option<(DailyReport, ReportNav)>> {
+        let ids = self.store.report_ids()?;
+        
+        let index = ids.iter().position(|id| id == key).or_else(|| {
+            // 按日期查找（ID 以日期开头，已按从新到旧排序）
+            if NaiveDate::parse_from_str(key, "%Y-%m-%d").is_ok() {
+                ids.iter().position(|id| id.starts_with(key))
+            } else {
+                None
+            }
+        });
+        
+        let Some(index) = index else {
+            return Ok(None);
+        };
+        
+        let Some(report) = self.store.load(&ids[index])? else {
+            return Ok(None);
+        };
+        
+        let nav = ReportNav {
+            prev: ids.get(index + 1).cloned(),
+            next: index.checked_sub(1).and_then(|i| ids.get(i)).cloned(),
+        };
+        
+        Ok(Some((report, nav)))
+    }
 
-        let limit = limit.unwrap_or(10).min(50); // 最多50条
+    /// 单期日报的 HTML 内容，供各种 feed 格式共用
+    fn report_content_html(&self, report: &DailyReport) -> String {
+        let mut content = String::new();
+        content.push_str("<h2>📈 每日概览</h2>\n");
+        content.push_str(&format!("<p><strong>📰 新闻来源</strong>: {} 条技术资讯</p>\n", report.items.len()));
+        content.push_str(&format!("<p><strong>🤖 生成时间</strong>: {}</p>\n", report.date.format("%Y-%m-%d %H:%M:%S UTC")));
+        content.push_str("<hr>\n");
+        
+        // 添加摘要
+        content.push_str("<h2>📋 今日摘要</h2>\n");
+        content.push_str(&markdown::render_markdown(&report.summary));
+        content.push_str("<hr>\n");
+        
+        // 添加新闻链接列表
+        if !report.items.is_empty() {
+            content.push_str("<h2>🔗 相关链接</h2>\n<ul>\n");
+            for item in &report.items {
+                content.push_str(&format!(
+                    "<li>{} - <em>{}</em></li>\n",
+                    report_page::render_external_link(&item.link, &item.title),
+                    escape_html(&item.source)
+                ));
+            }
+            content.push_str("</ul>\n");
+        }
         
-        for entry in entries.into_iter().take(limit) {
-            match std::fs::read_to_string(entry.path()) {
-                Ok(content) => {
-                    match serde_json::from_str::<DailyReport>(&content) {
-                        Ok(report) => reports.push(report),
-                        Err(e) => warn!("解析报告文件失败 {:?}: {}", entry.path(), e),
-                    }
+        content
+    }
+
+    /// feed 的标题、描述和自身地址
+    fn feed_meta(&self, kind: FeedKind) -> FeedMeta {
+        let (title, description) = match &kind {
+            FeedKind::Reports => (self.config().title.clone(), self.config().description.clone()),
+            FeedKind::Items => (
+                format!("{} · 单条新闻", self.config().title),
+                self.config().description.clone(),
+            ),
+            FeedKind::Source(name) => (
+                format!("{} · 来源: {}", self.config().title, name),
+                format!("{}（仅包含来源为 {} 的新闻）", self.config().description, name),
+            ),
+            FeedKind::Tag(tag) => (
+                format!("{} · 标签: {}", self.config().title, tag),
+                format!("{}（仅包含标签为 {} 的新闻）", self.config().description, tag),
+            ),
+        };
+        FeedMeta {
+            title,
+            description,
+            kind,
+        }
+    }
+
+    /// 每期日报对应一个条目
+    fn report_entry(&self, report: &DailyReport) -> FeedEntry {
+        FeedEntry {
+            guid: format!("rust-daily-{}", report.id),
+            urn: format!("urn:rust-daily:{}", report.id),
+            title: report.title(),
+            link: self.report_link(report),
+            content_html: self.report_content_html(report),
+            date: report.date,
+            categories: Vec::new(),
+            related: None,
+        }
+    }
+
+    /// 每条属于 `kind` 的新闻对应一个条目，同一条新闻出现在多期日报中时只保留最新一期
+    fn item_entries(&self, reports: &[DailyReport], kind: &FeedKind, limit: usize) -> Vec<FeedEntry> {
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        
+        for report in reports {
+            for item in &report.items {
+                if entries.len() >= limit {
+                    return entries;
+                }
+                
+                if !kind.matches(item) {
+                    continue;
                 }
-                Err(e) => warn!("读取报告文件失败 {:?}: {}", entry.path(), e),
+                
+                let id = item.id();
+                if !seen.insert(id.clone()) {
+                    continue;
+                }
+                
+                let report_link = self.report_link(report);
+                entries.push(FeedEntry {
+                    guid: format!("rust-daily-item-{}", id),
+                    urn: format!("urn:rust-daily:item:{}", id),
+                    title: item.title.clone(),
+                    // 原文地址来自第三方 feed，不是 http/https 时改为指向所属日报
+                    link: report_page::safe_external_url(&item.link).unwrap_or_else(|| report_link.clone()),
+                    content_html: Self::item_content_html(item, report, &report_link),
+                    date: report.date,
+                    categories: std::iter::once(item.source.clone())
+                        .chain(item.tags.iter().cloned())
+                        .collect(),
+                    related: Some(report_link),
+                });
             }
         }
+        
+        entries
+    }
 
-        Ok(reports)
+    /// 单条新闻的 HTML 内容：有 AI 摘要时使用摘要，否则使用清理后的原文描述
+    fn item_content_html(item: &NewsItem, report: &DailyReport, report_link: &str) -> String {
+        let mut content = match &item.ai_summary {
+            Some(summary) => markdown::render_markdown(summary),
+            None => format!("<div>{}</div>\n", markdown::sanitize_html(&item.description)),
+        };
+        content.push_str(&format!(
+            "<p><em>{}</em> · 收录于 <a href=\"{}\">{}</a></p>\n",
+            escape_html(&item.source),
+            escape_html(report_link),
+            escape_html(&report.title())
+        ));
+        content
+    }
+
+    pub(crate) fn report_link(&self, report: &DailyReport) -> String {
+        format!("{}/reports/{}", self.config().base_url, report.id)
     }
 
     /// 生成 RSS XML
-    fn generate_rss(&self, reports: Vec<DailyReport>) -> Result<String> {
+    fn generate_rss(&self, meta: &FeedMeta, entries: Vec<FeedEntry>) -> Result<String> {
+        let newest = entries.iter().map(|entry| entry.date).max();
         let mut channel = ChannelBuilder::default()
-            .title(&self.config.title)
-            .link(&self.config.base_url)
-            .description(&self.config.description)
-            .language(Some(self.config.language.clone()))
+            .title(&meta.title)
+            .link(&self.config().base_url)
+            .description(&meta.description)
+            .language(Some(self.config().language.clone()))
             .generator(Some("Rust Daily Generator v1.0".to_string()))
-            .pub_date(Utc::now().to_rfc2822())
-            .last_build_date(Utc::now().to_rfc2822())
+            .pub_date(newest.map(|date| date.to_rfc2822()))
+            .last_build_date(newest.map(|date| date.to_rfc2822()))
             .ttl("60".to_string()) // 1小时更新一次
             .build();
 
-        let mut items = Vec::new();
-
-        for report in reports {
-            // 为每个报告创建一个 RSS item
-            let title = format!("【Rust日报】{}", report.date.format("%Y-%m-%d"));
-            let link = format!("{}/reports/{}", self.config.base_url, report.date.format("%Y-%m-%d"));
-            let guid = format!("rust-daily-{}", report.date.format("%Y-%m-%d"));
-            
-            // 构建描述内容
-            let mut description = String::new();
-            description.push_str(&format!("<h2>📈 每日概览</h2>\n"));
-            description.push_str(&format!("<p><strong>📰 新闻来源</strong>: {} 条技术资讯</p>\n", report.items.len()));
-            description.push_str(&format!("<p><strong>🤖 生成时间</strong>: {}</p>\n", report.date.format("%Y-%m-%d %H:%M:%S UTC")));
-            description.push_str("<hr>\n");
-            
-            // 添加摘要
-            description.push_str("<h2>📋 今日摘要</h2>\n");
-            description.push_str(&self.markdown_to_html(&report.summary));
-            description.push_str("<hr>\n");
-            
-            // 添加新闻链接列表
-            if !report.items.is_empty() {
-                description.push_str("<h2>🔗 相关链接</h2>\n<ul>\n");
-                for item in &report.items {
-                    description.push_str(&format!(
-                        "<li><a href=\"{}\" target=\"_blank\">{}</a> - <em>{}</em></li>\n",
-                        item.link, item.title, item.source
-                    ));
+        let items = entries
+            .into_iter()
+            .map(|entry| {
+                let mut item = ItemBuilder::default()
+                    .title(entry.title)
+                    .link(entry.link)
+                    .description(entry.content_html)
+                    .pub_date(entry.date.to_rfc2822())
+                    .guid(rss::Guid {
+                        value: entry.guid,
+                        permalink: false,
+                    })
+                    .categories(
+                        entry.categories
+                            .into_iter()
+                            .map(|name| rss::Category { name, domain: None })
+                            .collect::<Vec<_>>(),
+                    )
+                    .build();
+                
+                // RSS 没有相关链接元素，借用 atom:link 表示
+                if let Some(related) = entry.related {
+                    item.set_atom_ext(rss::extension::atom::AtomExtension {
+                        links: vec![atom_syndication::LinkBuilder::default()
+                            .href(related)
+                            .rel("related")
+                            .mime_type(Some("text/html".to_string()))
+                            .build()],
+                    });
                 }
-                description.push_str("</ul>\n");
-            }
-            
-            let item = ItemBuilder::default()
-                .title(title)
-                .link(link)
-                .description(description)
-                .pub_date(report.date.to_rfc2822())
-                .guid(rss::Guid {
-                    value: guid,
-                    permalink: false,
-                })
-                .build();
-
-            items.push(item);
-        }
+                item
+            })
+            .collect::<Vec<_>>();
 
         channel.set_items(items);
         Ok(channel.to_string())
     }
 
-    /// 简单的 Markdown 到 HTML 转换
-    fn markdown_to_html(&self, markdown: &str) -> String {
-        let mut html = markdown.to_string();
+    /// 生成 Atom 1.0 XML
+    fn generate_atom(&self, meta: &FeedMeta, entries: Vec<FeedEntry>) -> Result<String> {
+        let updated = entries
+            .iter()
+            .map(|entry| entry.date)
+            .max()
+            .unwrap_or_else(Utc::now);
         
-        // 转换标题
-        html = html.replace("### ", "<h3>").replace("\n", "</h3>\n");
-        html = html.replace("## ", "<h2>").replace("\n", "</h2>\n");
-        html = html.replace("# ", "<h1>").replace("\n", "</h1>\n");
+        let entries: Vec<atom_syndication::Entry> = entries
+            .into_iter()
+            .map(|entry| {
+                let mut links = vec![atom_syndication::LinkBuilder::default()
+                    .href(entry.link)
+                    .rel("alternate")
+                    .mime_type(Some("text/html".to_string()))
+                    .build()];
+                if let Some(related) = entry.related {
+                    links.push(
+                        atom_syndication::LinkBuilder::default()
+                            .href(related)
+                            .rel("related")
+                            .mime_type(Some("text/html".to_string()))
+                            .build(),
+                    );
+                }
+                
+                atom_syndication::EntryBuilder::default()
+                    .id(entry.urn)
+                    .title(entry.title)
+                    .updated(entry.date)
+                    .published(Some(entry.date.into()))
+                    .links(links)
+                    .categories(
+                        entry.categories
+                            .into_iter()
+                            .map(|term| atom_syndication::CategoryBuilder::default().term(term).build())
+                            .collect::<Vec<_>>(),
+                    )
+                    .content(Some(
+                        atom_syndication::ContentBuilder::default()
+                            .value(Some(entry.content_html))
+                            .content_type(Some("html".to_string()))
+                            .build(),
+                    ))
+                    .build()
+            })
+            .collect();
         
-        // 转换列表
-        let lines: Vec<&str> = html.lines().collect();
-        let mut result = Vec::new();
-        let mut in_list = false;
+        let feed = atom_syndication::FeedBuilder::default()
+            .id(meta.kind.atom_id(&self.config().base_url))
+            .title(meta.title.as_str())
+            .subtitle(Some(meta.description.as_str().into()))
+            .updated(updated)
+            .lang(Some(self.config().language.clone()))
+            .generator(Some(atom_syndication::Generator {
+                value: "Rust Daily Generator".to_string(),
+                uri: None,
+                version: Some("1.0".to_string()),
+            }))
+            .link(
+                atom_syndication::LinkBuilder::default()
+                    .href(meta.kind.self_url(&self.config().base_url, FeedFormat::Atom))
+                    .rel("self")
+                    .mime_type(Some("application/atom+xml".to_string()))
+                    .build(),
+            )
+            .link(
+                atom_syndication::LinkBuilder::default()
+                    .href(self.config().base_url.clone())
+                    .rel("alternate")
+                    .build(),
+            )
+            .entries(entries)
+            .build();
         
-        for line in lines {
-            if line.trim().starts_with("- ") {
-                if !in_list {
-                    result.push("<ul>".to_string());
-                    in_list = true;
-                }
-                let item_text = line.trim_start_matches("- ").trim();
-                result.push(format!("<li>{}</li>", item_text));
-            } else {
-                if in_list {
-                    result.push("</ul>".to_string());
-                    in_list = false;
+        Ok(feed.to_string())
+    }
+
+    /// 生成 JSON Feed 1.1
+    fn generate_json_feed(&self, meta: &FeedMeta, entries: Vec<FeedEntry>) -> Result<String> {
+        let items: Vec<serde_json::Value> = entries
+            .into_iter()
+            .map(|entry| {
+                let mut item = serde_json::json!({
+                    "id": entry.guid,
+                    "url": entry.link,
+                    "title": entry.title,
+                    "content_html": entry.content_html,
+                    "date_published": entry.date.to_rfc3339(),
+                });
+                if !entry.categories.is_empty() {
+                    item["tags"] = serde_json::json!(entry.categories);
                 }
-                if !line.trim().is_empty() {
-                    result.push(format!("<p>{}</p>", line));
+                // JSON Feed 没有相关链接字段，使用自定义扩展
+                if let Some(related) = entry.related {
+                    item["_rust_daily"] = serde_json::json!({ "report_url": related });
                 }
-            }
-        }
+                item
+            })
+            .collect();
         
-        if in_list {
-            result.push("</ul>".to_string());
-        }
+        let feed = serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": meta.title,
+            "home_page_url": self.config().base_url,
+            "feed_url": meta.kind.self_url(&self.config().base_url, FeedFormat::Json),
+            "description": meta.description,
+            "language": self.config().language,
+            "items": items,
+        });
         
-        result.join("\n")
+        Ok(serde_json::to_string_pretty(&feed)?)
     }
 }
 
@@ -238,106 +651,581 @@ impl Clone for RssServer {
     fn clone(&self) -> Self {
         Self {
             config: self.config.clone(),
-            reports_dir: self.reports_dir.clone(),
+            store: self.store.clone(),
+            cache: self.cache.clone(),
+            admin: self.admin.clone(),
+            shared: self.shared.clone(),
+            local_scheduler: self.local_scheduler,
+        }
+    }
+}
+
+/// Feed 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "rss" => Some(Self::Rss),
+            "atom" => Some(Self::Atom),
+            "json" => Some(Self::Json),
+            _ => None,
         }
     }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Rss => "rss",
+            Self::Atom => "atom",
+            Self::Json => "json",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Rss => "application/rss+xml; charset=utf-8",
+            Self::Atom => "application/atom+xml; charset=utf-8",
+            Self::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+}
+
+/// Feed 种类
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FeedKind {
+    /// 每期日报一个条目
+    Reports,
+    /// 每条新闻一个条目
+    Items,
+    /// 指定来源的新闻
+    Source(String),
+    /// 带指定标签的新闻
+    Tag(String),
+}
+
+impl FeedKind {
+    /// 来源和标签统一为小写，使同一 feed 只对应一个缓存项
+    fn normalized(&self) -> Self {
+        match self {
+            Self::Source(name) => Self::Source(name.to_ascii_lowercase()),
+            Self::Tag(tag) => Self::Tag(tag.to_ascii_lowercase()),
+            kind => kind.clone(),
+        }
+    }
+
+    /// 换成日报中最新一条匹配新闻所用的来源或标签名称，没有匹配的新闻时返回 `None`
+    fn resolve(&self, reports: &[DailyReport]) -> Option<Self> {
+        let mut items = reports.iter().flat_map(|report| &report.items);
+        match self {
+            Self::Reports | Self::Items => Some(self.clone()),
+            Self::Source(name) => items
+                .find(|item| item.source.eq_ignore_ascii_case(name))
+                .map(|item| Self::Source(item.source.clone())),
+            Self::Tag(tag) => items
+                .find_map(|item| item.tags.iter().find(|item_tag| item_tag.eq_ignore_ascii_case(tag)))
+                .map(|tag| Self::Tag(tag.clone())),
+        }
+    }
+
+    fn limit(&self, limit: Option<usize>) -> usize {
+        match self {
+            Self::Reports => limit.unwrap_or(10).min(50), // 最多50期
+            _ => limit.unwrap_or(30).min(200),            // 最多200条
+        }
+    }
+
+    /// 按新闻输出的 feed 的路径
+    fn path(&self) -> Option<String> {
+        match self {
+            Self::Reports => None,
+            Self::Items => Some("/feed/items".to_string()),
+            Self::Source(name) => Some(format!("/feed/source/{}", encode_path_segment(name))),
+            Self::Tag(tag) => Some(format!("/feed/tag/{}", encode_path_segment(tag))),
+        }
+    }
+
+    /// feed 自身的地址
+    fn self_url(&self, base_url: &str, format: FeedFormat) -> String {
+        match (self.path(), format) {
+            (None, FeedFormat::Rss) => format!("{}/feed", base_url),
+            (None, FeedFormat::Atom) => format!("{}/atom.xml", base_url),
+            (None, FeedFormat::Json) => format!("{}/feed.json", base_url),
+            (Some(path), FeedFormat::Rss) => format!("{}{}", base_url, path),
+            (Some(path), format) => format!("{}{}?format={}", base_url, path, format.name()),
+        }
+    }
+
+    /// Atom feed 的唯一标识
+    fn atom_id(&self, base_url: &str) -> String {
+        match self.path() {
+            None => format!("{}/", base_url),
+            Some(path) => format!("{}{}", base_url, path),
+        }
+    }
+
+    /// 新闻是否属于该 feed，来源和标签不区分大小写
+    fn matches(&self, item: &NewsItem) -> bool {
+        match self {
+            Self::Reports | Self::Items => true,
+            Self::Source(name) => item.source.eq_ignore_ascii_case(name),
+            Self::Tag(tag) => item.tags.iter().any(|item_tag| item_tag.eq_ignore_ascii_case(tag)),
+        }
+    }
+}
+
+/// 编码 URL 路径中的一段
+fn encode_path_segment(segment: &str) -> String {
+    use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+    
+    utf8_percent_encode(segment, NON_ALPHANUMERIC).to_string()
+}
+
+/// feed 级别的元数据
+struct FeedMeta {
+    title: String,
+    description: String,
+    kind: FeedKind,
+}
+
+/// 与输出格式无关的 feed 条目
+struct FeedEntry {
+    /// RSS guid 和 JSON Feed id
+    guid: String,
+    /// Atom entry id
+    urn: String,
+    title: String,
+    link: String,
+    content_html: String,
+    date: DateTime<Utc>,
+    categories: Vec<String>,
+    /// 相关页面，例如新闻所属的日报
+    related: Option<String>,
 }
 
 /// RSS feed 处理器
 async fn rss_feed_handler(
+    state: axum::extract::State<RssServer>,
+    query: Query<RssFeedQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    feed_response(state, query, headers, FeedKind::Reports, FeedFormat::Rss)
+}
+
+/// Atom feed 处理器
+async fn atom_feed_handler(
+    state: axum::extract::State<RssServer>,
+    query: Query<RssFeedQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    feed_response(state, query, headers, FeedKind::Reports, FeedFormat::Atom)
+}
+
+/// JSON Feed 处理器
+async fn json_feed_handler(
+    state: axum::extract::State<RssServer>,
+    query: Query<RssFeedQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    feed_response(state, query, headers, FeedKind::Reports, FeedFormat::Json)
+}
+
+/// 单条新闻 feed 处理器，默认输出 RSS
+async fn items_feed_handler(
+    state: axum::extract::State<RssServer>,
+    query: Query<RssFeedQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    feed_response(state, query, headers, FeedKind::Items, FeedFormat::Rss)
+}
+
+/// 指定来源的新闻 feed 处理器
+async fn source_feed_handler(
+    state: axum::extract::State<RssServer>,
+    axum::extract::Path(name): axum::extract::Path<String>,
+    query: Query<RssFeedQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    feed_response(state, query, headers, FeedKind::Source(name), FeedFormat::Rss)
+}
+
+/// 指定标签的新闻 feed 处理器
+async fn tag_feed_handler(
+    state: axum::extract::State<RssServer>,
+    axum::extract::Path(tag): axum::extract::Path<String>,
+    query: Query<RssFeedQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    feed_response(state, query, headers, FeedKind::Tag(tag), FeedFormat::Rss)
+}
+
+/// 生成 feed 响应，`?format=` 参数优先于路由默认格式
+fn feed_response(
     axum::extract::State(server): axum::extract::State<RssServer>,
     Query(params): Query<RssFeedQuery>,
-) -> impl IntoResponse {
-    match server.load_reports(params.limit) {
-        Ok(reports) => {
-            match server.generate_rss(reports) {
-                Ok(rss_xml) => {
-                    let mut headers = axum::http::HeaderMap::new();
-                    headers.insert(
-                        header::CONTENT_TYPE,
-                        "application/rss+xml; charset=utf-8".parse().unwrap(),
-                    );
-                    headers.insert(
-                        header::CACHE_CONTROL,
-                        "public, max-age=3600".parse().unwrap(), // 缓存1小时
-                    );
-                    
-                    (StatusCode::OK, headers, rss_xml).into_response()
-                }
-                Err(e) => {
-                    warn!("生成 RSS 失败: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, "生成 RSS 失败").into_response()
-                }
+    request_headers: HeaderMap,
+    kind: FeedKind,
+    default_format: FeedFormat,
+) -> axum::response::Response {
+    let format = match params.format.as_deref() {
+        None => default_format,
+        Some(value) => match FeedFormat::parse(value) {
+            Some(format) => format,
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    format!("不支持的 feed 格式: {} (可选: rss, atom, json)", value),
+                ).into_response();
             }
-        }
+        },
+    };
+    
+    let feed = match server.cached_feed(kind, format, params.limit) {
+        Ok(Some(feed)) => feed,
+        Ok(None) => return (StatusCode::NOT_FOUND, "没有找到该来源或标签的新闻").into_response(),
         Err(e) => {
-            warn!("加载报告失败: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "加载报告失败").into_response()
+            warn!("生成 feed 失败: {}", e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, "生成 feed 失败").into_response();
         }
+    };
+    
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert(header::ETAG, feed.etag.parse().unwrap());
+    if let Some(last_modified) = feed.last_modified {
+        headers.insert(header::LAST_MODIFIED, http_date(last_modified).parse().unwrap());
     }
+    headers.insert(
+        header::CACHE_CONTROL,
+        "public, max-age=300".parse().unwrap(), // 依靠 ETag 重新验证
+    );
+    
+    if is_not_modified(&request_headers, &feed) {
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+    
+    headers.insert(
+        header::CONTENT_TYPE,
+        format.content_type().parse().unwrap(),
+    );
+    (StatusCode::OK, headers, feed.body).into_response()
 }
 
-/// API 报告列表处理器
-async fn api_reports_handler(
+/// 判断条件请求是否可以返回 304，`If-None-Match` 优先于 `If-Modified-Since`
+fn is_not_modified(request_headers: &HeaderMap, feed: &CachedFeed) -> bool {
+    if let Some(if_none_match) = request_headers.get(header::IF_NONE_MATCH) {
+        let Ok(if_none_match) = if_none_match.to_str() else {
+            return false;
+        };
+        return if_none_match
+            .split(',')
+            .map(|tag| tag.trim())
+            .any(|tag| tag == "*" || tag.trim_start_matches("W/") == feed.etag);
+    }
+    
+    if let (Some(if_modified_since), Some(last_modified)) =
+        (request_headers.get(header::IF_MODIFIED_SINCE), feed.last_modified)
+    {
+        if let Some(since) = if_modified_since
+            .to_str()
+            .ok()
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        {
+            // HTTP 日期只精确到秒
+            return last_modified.timestamp() <= since.timestamp();
+        }
+    }
+    
+    false
+}
+
+/// 根据内容生成强 ETag
+fn content_etag(body: &str) -> String {
+    use sha2::{Digest, Sha256};
+    
+    // 使用 SHA-256 而不是标准库哈希，升级 Rust 版本后 ETag 保持不变
+    let digest: String = Sha256::digest(body.as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("\"{}-{:x}\"", digest, body.len())
+}
+
+/// HTTP 日期格式（RFC 7231 IMF-fixdate）
+fn http_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// 单期日报处理器：`/reports/{id|date}` 返回 HTML，`.json` 和 `.md` 后缀返回对应格式
+async fn report_page_handler(
     axum::extract::State(server): axum::extract::State<RssServer>,
-    Query(params): Query<RssFeedQuery>,
+    axum::extract::Path(key): axum::extract::Path<String>,
 ) -> impl IntoResponse {
-    match server.load_reports(params.limit) {
-        Ok(reports) => {
-            let mut headers = axum::http::HeaderMap::new();
-            headers.insert(
-                header::CONTENT_TYPE,
-                "application/json; charset=utf-8".parse().unwrap(),
-            );
-            
-            let response_data = serde_json::json!({
-                "status": "success",
-                "count": reports.len(),
-                "reports": reports
-            });
-            
-            (StatusCode::OK, headers, serde_json::to_string_pretty(&response_data).unwrap()).into_response()
+    let (key, format) = if let Some(key) = key.strip_suffix(".json") {
+        (key, "json")
+    } else if let Some(key) = key.strip_suffix(".md") {
+        (key, "md")
+    } else {
+        (key.as_str(), "html")
+    };
+    
+    let (report, nav) = match server.find_report(key) {
+        Ok(Some(found)) => found,
+        Ok(None) => {
+            return match format {
+                "json" => api::ApiError::not_found(format!("未找到日报: {}", key)).into_response(),
+                "md" => (StatusCode::NOT_FOUND, format!("未找到日报: {}", key)).into_response(),
+                _ => (
+                    StatusCode::NOT_FOUND,
+                    [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+                    report_page::render_not_found(&server.config(), key),
+                ).into_response(),
+            };
         }
         Err(e) => {
             warn!("加载报告失败: {}", e);
-            let error_response = serde_json::json!({
-                "status": "error",
-                "message": format!("加载报告失败: {}", e)
-            });
-            (StatusCode::INTERNAL_SERVER_ERROR, serde_json::to_string(&error_response).unwrap()).into_response()
+            return (StatusCode::INTERNAL_SERVER_ERROR, "加载报告失败").into_response();
+        }
+    };
+    
+    match format {
+        "json" => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+            serde_json::to_string_pretty(&report).unwrap(),
+        ).into_response(),
+        "md" => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+            crate::format_daily_report(&report),
+        ).into_response(),
+        _ => {
+            let summary_html = markdown::render_markdown(&report.summary);
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+                report_page::render_report_page(&server.config(), &report, &summary_html, &nav),
+            ).into_response()
         }
     }
 }
 
-/// RSS 服务器配置构建器
-impl RssServerConfig {
-    pub fn from_env() -> Self {
-        let mut config = Self::default();
-        
-        if let Ok(host) = std::env::var("RSS_HOST") {
-            config.host = host;
+/// `/health` 查询参数
+#[derive(Debug, Deserialize)]
+struct HealthQuery {
+    verbose: Option<String>,
+}
+
+/// 健康检查：默认只返回 "OK"，`verbose=1` 时返回各项检查的 JSON，有失败项时状态码为 503
+///
+/// 不会请求 LLM 接口；只有携带管理令牌时才返回各项的详细说明，其中可能包含路径和错误信息。
+async fn health_handler(
+    axum::extract::State(server): axum::extract::State<RssServer>,
+    Query(query): Query<HealthQuery>,
+    request_headers: HeaderMap,
+) -> axum::response::Response {
+    let verbose = matches!(query.verbose.as_deref(), Some("1" | "true" | "yes"));
+    if !verbose {
+        return "OK".into_response();
+    }
+    
+    let config = match Config::resolve() {
+        Ok(config) => config,
+        Err(e) => {
+            warn!("健康检查加载配置失败: {}", e);
+            return api::ApiError::internal("加载配置失败").into_response();
         }
-        
-        if let Ok(port) = std::env::var("RSS_PORT") {
-            if let Ok(port) = port.parse() {
-                config.port = port;
+    };
+    let options = health::CheckOptions {
+        probe_llm: false,
+        schedules: server.local_scheduler,
+    };
+    let mut report = health::run_checks(&config, &server.store, options).await;
+    if !admin::is_authorized(&server.config(), &request_headers) {
+        report = report.without_details();
+    }
+    let status = if report.is_healthy() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    api::json_response(status, &serde_json::to_value(&report).unwrap())
+}
+
+/// 记录 HTTP 请求数和耗时，按路由模板而不是具体路径分组
+async fn track_http_metrics(request: axum::extract::Request, next: axum::middleware::Next) -> axum::response::Response {
+    let started = std::time::Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    
+    let response = next.run(request).await;
+    telemetry::record_http_request(&method, &route, response.status().as_u16(), started.elapsed());
+    response
+}
+
+/// 等待 Ctrl+C 或 SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("监听 Ctrl+C 信号失败: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!("监听 SIGTERM 信号失败: {}", e);
+                std::future::pending::<()>().await;
             }
         }
+    };
+    
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    
+    info!("收到停止信号，等待进行中的请求完成...");
+}
+
+/// SIGHUP 信号，收到时重新加载配置；非 Unix 平台上永远不会触发
+pub struct ReloadSignal {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl ReloadSignal {
+    pub fn new() -> Self {
+        #[cfg(unix)]
+        {
+            let signal = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+                .map_err(|e| warn!("监听 SIGHUP 信号失败: {}", e))
+                .ok();
+            Self { signal }
+        }
         
-        if let Ok(base_url) = std::env::var("RSS_BASE_URL") {
-            config.base_url = base_url;
-        } else {
-            config.base_url = format!("http://{}:{}", config.host, config.port);
+        #[cfg(not(unix))]
+        Self {}
+    }
+    
+    /// 等待下一次 SIGHUP
+    pub async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            if signal.recv().await.is_some() {
+                return;
+            }
         }
         
-        if let Ok(title) = std::env::var("RSS_TITLE") {
-            config.title = title;
+        std::future::pending::<()>().await;
+    }
+}
+
+/// 在 Unix domain socket 上提供服务，供反向代理使用
+#[cfg(unix)]
+async fn serve_unix<F>(socket_path: &std::path::Path, app: Router, shutdown: F) -> Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    use hyper_util::rt::{TokioExecutor, TokioIo};
+    use hyper_util::server::conn::auto::Builder;
+    use hyper_util::server::graceful::GracefulShutdown;
+    use std::os::unix::fs::FileTypeExt;
+    use tower::ServiceExt;
+    
+    // 清理上次未正常退出留下的 socket 文件，路径上是其他类型的文件时拒绝启动
+    match std::fs::symlink_metadata(socket_path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(socket_path)?,
+        Ok(_) => {
+            return Err(anyhow::anyhow!(
+                "{} 已存在且不是 Unix socket，请检查 unix_socket 配置",
+                socket_path.display()
+            ));
         }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    let listener = tokio::net::UnixListener::bind(socket_path)?;
+    let graceful = GracefulShutdown::new();
+    let builder = Builder::new(TokioExecutor::new());
+    
+    tokio::pin!(shutdown);
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    warn!("接受 Unix socket 连接失败: {}", e);
+                    continue;
+                }
+            },
+            _ = &mut shutdown => break,
+        };
+        
+        let app = app.clone();
+        let service = hyper::service::service_fn(move |request: axum::extract::Request<hyper::body::Incoming>| {
+            app.clone().oneshot(request)
+        });
+        let connection = builder.serve_connection_with_upgrades(TokioIo::new(stream), service).into_owned();
+        let connection = graceful.watch(connection);
+        
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                debug!("Unix socket 连接出错: {}", e);
+            }
+        });
+    }
+    
+    drop(listener);
+    graceful.shutdown().await;
+    let _ = std::fs::remove_file(socket_path);
+    
+    info!("✅ RSS 服务器已停止");
+    Ok(())
+}
+
+/// RSS 服务器配置构建器
+impl RssServerConfig {
+    /// 解析监听地址，支持 IPv4、IPv6（可带方括号）和主机名
+    pub async fn bind_addr(&self) -> Result<SocketAddr> {
+        let host = self.host.trim_start_matches('[').trim_end_matches(']');
         
-        if let Ok(description) = std::env::var("RSS_DESCRIPTION") {
-            config.description = description;
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(SocketAddr::new(ip, self.port));
         }
         
-        config
+        tokio::net::lookup_host((host, self.port))
+            .await?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("无法解析监听地址: {}", self.host))
+    }
+    
+    /// 由 `[server]` 配置创建，未设置 `base_url` 时根据监听地址推导
+    pub fn from_config(config: &ServerConfig) -> Self {
+        Self {
+            host: config.host.clone(),
+            port: config.port,
+            base_url: config.effective_base_url(),
+            title: config.title.clone(),
+            description: config.description.clone(),
+            unix_socket: config.unix_socket.clone(),
+            admin_token: config.admin_token.clone(),
+            ..Self::default()
+        }
     }
 }
\ No newline at end of file
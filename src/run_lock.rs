@@ -0,0 +1,56 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// 跨进程的日报生成锁
+///
+/// 守护进程、RSS 服务器和命令行共用 `~/.rust-daily/generation.lock`，
+/// 同一时间只允许一个进程生成日报。锁由操作系统持有，进程退出后自动释放。
+#[derive(Debug)]
+pub struct RunLock {
+    _file: File,
+}
+
+impl RunLock {
+    fn path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
+            .join(".rust-daily")
+            .join("generation.lock")
+    }
+
+    /// 尝试获取锁，已被其他进程持有时返回 `None`
+    pub fn try_acquire() -> Result<Option<Self>> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok(None),
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+        }
+
+        // 记录持有者，便于排查
+        file.set_len(0)?;
+        writeln!(file, "pid={} started_at={}", std::process::id(), Utc::now().to_rfc3339())?;
+        Ok(Some(Self { _file: file }))
+    }
+
+    /// 最近一次获取锁的进程信息，用于提示谁在占用锁
+    pub fn holder() -> Option<String> {
+        fs::read_to_string(Self::path())
+            .ok()
+            .map(|content| content.trim().to_string())
+            .filter(|content| !content.is_empty())
+    }
+}
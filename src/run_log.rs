@@ -0,0 +1,197 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use tracing::warn;
+
+use crate::pipeline::RunOutcome;
+use crate::report_store::write_atomic;
+
+/// 日志超过该条数时压缩
+const MAX_RECORDS: usize = 2000;
+/// 压缩后保留的最近记录数
+const KEEP_RECORDS: usize = 1000;
+
+/// 定时任务的执行结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskRunStatus {
+    /// 生成了新日报
+    Succeeded,
+    /// 没有新内容，未生成日报
+    Skipped,
+    Failed,
+}
+
+impl TaskRunStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskRunStatus::Succeeded => "succeeded",
+            TaskRunStatus::Skipped => "skipped",
+            TaskRunStatus::Failed => "failed",
+        }
+    }
+}
+
+/// 一次定时任务或管理 API 任务的执行记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRun {
+    pub id: String,
+    /// 定时任务名称
+    pub job: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub status: TaskRunStatus,
+    /// 从 RSS 源获取的新闻数
+    #[serde(default)]
+    pub fetched: usize,
+    /// 去重后待处理的新闻数
+    #[serde(default)]
+    pub filtered: usize,
+    /// 写入日报的新闻数
+    #[serde(default)]
+    pub summarized: usize,
+    #[serde(default)]
+    pub report_id: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl TaskRun {
+    /// 开始一条执行记录，在写入结果前状态为失败
+    pub fn start(job: &str) -> Self {
+        let started_at = Utc::now();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            job: job.to_string(),
+            started_at,
+            finished_at: started_at,
+            status: TaskRunStatus::Failed,
+            fetched: 0,
+            filtered: 0,
+            summarized: 0,
+            report_id: None,
+            error: None,
+        }
+    }
+
+    /// 按日报生成结果填写状态和新闻数，定时任务和管理 API 共用这一映射
+    pub fn apply_outcome(&mut self, result: &Result<RunOutcome>) {
+        match result {
+            Ok(RunOutcome::Generated { report, fetched, filtered }) => {
+                self.status = TaskRunStatus::Succeeded;
+                self.fetched = *fetched;
+                self.filtered = *filtered;
+                self.summarized = report.items.len();
+                self.report_id = Some(report.id.clone());
+            }
+            Ok(RunOutcome::NothingNew { fetched }) => {
+                self.status = TaskRunStatus::Skipped;
+                self.fetched = *fetched;
+            }
+            Ok(RunOutcome::NoNews) => {
+                self.status = TaskRunStatus::Failed;
+                self.error = Some("未获取到任何新闻".to_string());
+            }
+            Err(e) => {
+                self.status = TaskRunStatus::Failed;
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// 记录结束时间并写入执行日志，失败时只记录警告
+    pub fn save(mut self) {
+        self.finished_at = Utc::now();
+        if let Err(e) = RunLog::open().append(&self) {
+            warn!("保存任务执行记录失败: {}", e);
+        }
+    }
+
+    pub fn duration_secs(&self) -> i64 {
+        (self.finished_at - self.started_at).num_seconds()
+    }
+}
+
+/// 持久化的任务执行日志，每行一条 JSON 记录
+pub struct RunLog {
+    path: PathBuf,
+}
+
+impl RunLog {
+    /// 日志保存在 `~/.rust-daily/task_runs.jsonl`，与历史记录放在一起
+    pub fn open() -> Self {
+        let path = dirs::home_dir()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
+            .join(".rust-daily")
+            .join("task_runs.jsonl");
+        Self { path }
+    }
+
+    /// 追加一条记录，记录过多时只保留最近的部分
+    ///
+    /// 守护进程和 RSS 服务器可能同时写入，追加和压缩都在 `task_runs.jsonl.lock`
+    /// 的排他锁内进行。压缩会用新文件替换日志，因此锁加在单独的文件上，
+    /// 避免另一进程追加到被替换掉的旧文件而丢失记录。
+    pub fn append(&self, run: &TaskRun) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let lock = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.path.with_extension("jsonl.lock"))?;
+        lock.lock()?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(run)?)?;
+        drop(file);
+
+        let runs = self.runs()?;
+        if runs.len() > MAX_RECORDS {
+            let mut content = String::new();
+            for run in &runs[runs.len() - KEEP_RECORDS..] {
+                content.push_str(&serde_json::to_string(run)?);
+                content.push('\n');
+            }
+            write_atomic(&self.path, content.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// 任务最近一次成功执行（含没有新内容的执行）的开始时间
+    pub fn last_success(&self, job: &str) -> Result<Option<DateTime<Utc>>> {
+        Ok(self
+            .runs()?
+            .iter()
+            .rev()
+            .find(|run| run.job == job && run.status != TaskRunStatus::Failed && run.error.is_none())
+            .map(|run| run.started_at))
+    }
+
+    /// 全部记录，按执行顺序排列；无法解析的行会被跳过
+    pub fn runs(&self) -> Result<Vec<TaskRun>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+
+        let content = fs::read_to_string(&self.path)?;
+        let runs = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(run) => Some(run),
+                Err(e) => {
+                    warn!("跳过无法解析的任务记录: {}", e);
+                    None
+                }
+            })
+            .collect();
+        Ok(runs)
+    }
+}
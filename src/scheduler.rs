@@ -1,15 +1,158 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{error, info, warn};
+use std::future::Future;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
-use rig::client::{ProviderClient, CompletionClient};
+use uuid::Uuid;
 
-use crate::{Config, DailyGenerator, DailyReport, HistoryManager, RssFetcher};
+use crate::health::{self, CheckStatus};
+use crate::pipeline::{self, RunOptions, RunOutcome};
+use crate::report_store::ReportStore;
+use crate::rss_server::ReloadSignal;
+use crate::run_log::{RunLog, TaskRun, TaskRunStatus};
+use crate::run_lock::RunLock;
+use crate::state::{GenerationGuard, SharedState};
+use crate::telemetry;
+use crate::config::{OverlapPolicy, ScheduleConfig};
+use crate::{format_daily_report, Config, HistoryManager};
+
+/// 停止时等待进行中的生成任务的最长时间
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(60);
+/// 启动时展示的每个任务接下来的执行次数
+const NEXT_FIRE_TIMES: usize = 3;
+/// `--task-stats` 展示的最近执行记录数
+const RECENT_RUNS: usize = 5;
+/// 统计错过的执行次数时的上限，避免高频任务长时间停机后遍历过多
+const MAX_MISSED_COUNT: usize = 1000;
+
+/// 解析并校验过的定时任务
+#[derive(Debug, Clone)]
+pub struct JobSchedule {
+    pub config: ScheduleConfig,
+    cron: Cron,
+    timezone: Tz,
+}
+
+impl JobSchedule {
+    pub fn parse(config: ScheduleConfig) -> Result<Self> {
+        let timezone: Tz = config.timezone.parse().map_err(|_| {
+            anyhow::anyhow!("定时任务 {} 的时区无效: {}", config.name, config.timezone)
+        })?;
+        let cron = Cron::new(&config.cron)
+            .with_seconds_required()
+            .with_dom_and_dow()
+            .parse()
+            .map_err(|e| anyhow::anyhow!("定时任务 {} 的 cron 表达式无效 \"{}\": {}", config.name, config.cron, e))?;
+        if config.days == 0 {
+            return Err(anyhow::anyhow!("定时任务 {} 的 days 必须大于 0", config.name));
+        }
+        if config.catch_up_max_days == 0 {
+            return Err(anyhow::anyhow!("定时任务 {} 的 catch_up_max_days 必须大于 0", config.name));
+        }
+        
+        Ok(Self { config, cron, timezone })
+    }
+    
+    /// 接下来的若干次执行时间（任务所在时区）
+    pub fn next_fire_times(&self, count: usize) -> Vec<DateTime<Tz>> {
+        self.cron
+            .iter_after(Utc::now().with_timezone(&self.timezone))
+            .take(count)
+            .collect()
+    }
+    
+    /// `since` 之后、`now` 之前错过的执行次数（最多统计 `MAX_MISSED_COUNT` 次）
+    pub fn missed_runs(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> usize {
+        self.cron
+            .iter_after(since.with_timezone(&self.timezone))
+            .take_while(|time| time.with_timezone(&Utc) <= now)
+            .take(MAX_MISSED_COUNT)
+            .count()
+    }
+}
+
+/// 启动时对单个任务的处理决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StartupAction {
+    /// 立即执行，获取最近 `days` 天的新闻
+    Run { days: u64 },
+    /// 不执行
+    Wait,
+}
+
+/// 根据上次成功执行时间决定启动时是否补执行
+fn startup_action(schedule: &JobSchedule, last_success: Option<DateTime<Utc>>, now: DateTime<Utc>) -> StartupAction {
+    let config = &schedule.config;
+    let Some(last) = last_success else {
+        return if config.run_on_start {
+            StartupAction::Run { days: config.days }
+        } else {
+            StartupAction::Wait
+        };
+    };
+    
+    let missed = schedule.missed_runs(last, now);
+    if missed == 0 {
+        info!("任务 {} 上次执行于 {}，没有错过的执行", config.name, last.format("%Y-%m-%d %H:%M:%S UTC"));
+        return StartupAction::Wait;
+    }
+    if !config.catch_up {
+        warn!("任务 {} 停机期间错过了 {} 次执行，未开启 catch_up，等待下次执行", config.name, missed);
+        return StartupAction::Wait;
+    }
+    
+    // 回看窗口覆盖停机时间，但不超过 catch_up_max_days
+    let gap_days = ((now - last).num_seconds().max(0) as u64).div_ceil(86400);
+    let days = config.days.max(gap_days.min(config.catch_up_max_days));
+    info!(
+        "任务 {} 停机期间错过了 {} 次执行（上次执行于 {}），补执行最近 {} 天的新闻",
+        config.name,
+        missed,
+        last.format("%Y-%m-%d %H:%M:%S UTC"),
+        days
+    );
+    StartupAction::Run { days }
+}
+
+/// 解析配置中的所有定时任务，任何一个无效或名称重复都返回错误
+pub fn parse_schedules(config: &Config) -> Result<Vec<JobSchedule>> {
+    let mut schedules: Vec<JobSchedule> = Vec::new();
+    for schedule in config.effective_schedules() {
+        if schedule.name.trim().is_empty() {
+            return Err(anyhow::anyhow!("定时任务名称不能为空"));
+        }
+        if schedules.iter().any(|existing| existing.config.name == schedule.name) {
+            return Err(anyhow::anyhow!("定时任务名称重复: {}", schedule.name));
+        }
+        schedules.push(JobSchedule::parse(schedule)?);
+    }
+    Ok(schedules)
+}
+
+/// 每次执行定时任务时需要的上下文
+#[derive(Clone)]
+struct JobContext {
+    reports_dir: String,
+    shared: SharedState,
+    /// 最近一次成功加载的配置，重新加载失败时保持不变
+    config: Arc<RwLock<Config>>,
+}
+
+impl JobContext {
+    fn config(&self) -> Config {
+        self.config.read().unwrap().clone()
+    }
+}
 
 pub struct TaskScheduler {
     scheduler: JobScheduler,
-    reports_dir: String,
+    context: JobContext,
+    /// 已添加到调度器的任务及其 ID
+    jobs: Vec<(Uuid, JobSchedule)>,
 }
 
 impl TaskScheduler {
@@ -18,177 +161,375 @@ impl TaskScheduler {
         
         Ok(TaskScheduler {
             scheduler,
-            reports_dir,
+            context: JobContext {
+                reports_dir,
+                shared: SharedState::default(),
+                config: Arc::new(RwLock::new(Config::default())),
+            },
+            jobs: Vec::new(),
         })
     }
     
-    /// 启动定时任务调度器
+    /// 与同一进程内的 RSS 服务器共享生成锁和缓存失效通知
+    pub fn with_shared_state(mut self, shared: SharedState) -> Self {
+        self.context.shared = shared;
+        self
+    }
+    
+    /// 启动定时任务调度器，首次日报生成在后台执行
     pub async fn start(&mut self) -> Result<()> {
         info!("🚀 启动后端定时任务调度器");
         
-        // 添加 4 小时间隔的日报生成任务
-        self.add_daily_report_job().await?;
+        // 按配置添加定时任务，任何一个无效都拒绝启动
+        let config = Config::load()?;
+        let schedules = parse_schedules(&config)?;
+        *self.context.config.write().unwrap() = config;
+        for schedule in schedules {
+            self.add_job(schedule).await?;
+        }
+        
+        // 恢复上次异常退出遗留的待提交任务并按保留策略清理历史记录，
+        // 持有生成锁后再加载历史记录；其他进程正在生成时留给下次执行处理
+        if let Some(_guard) = self.context.shared.try_lock_generation()? {
+            let mut history_manager = HistoryManager::new()?;
+            let (committed, discarded) = history_manager.recover_pending_runs(&ReportStore::new(&self.context.reports_dir))?;
+            if committed + discarded > 0 {
+                info!("♻️ 已恢复遗留任务：提交 {} 个，放弃 {} 个", committed, discarded);
+            }
+            Self::apply_history_retention(&self.context.config());
+        }
         
         // 启动调度器
         self.scheduler.start().await.map_err(|e| anyhow::anyhow!("Failed to start scheduler: {}", e))?;
         
         info!("⏰ 定时任务调度器已启动");
-        info!("📅 日报生成任务将每 4 小时运行一次");
+        self.log_schedules();
+        
+        // 首次启动或停机期间错过执行的任务立即执行
+        let run_log = RunLog::open();
+        let now = Utc::now();
+        for (_, schedule) in &self.jobs {
+            let last_success = run_log.last_success(&schedule.config.name).unwrap_or_else(|e| {
+                warn!("读取任务 {} 的执行记录失败: {}", schedule.config.name, e);
+                None
+            });
+            if let StartupAction::Run { days } = startup_action(schedule, last_success, now) {
+                info!("🔄 立即执行任务 {} ...", schedule.config.name);
+                tokio::spawn(Self::run_scheduled_task(
+                    ScheduleConfig { days, ..schedule.config.clone() },
+                    self.context.clone(),
+                ));
+            }
+        }
         
-        // 立即执行一次任务（可选）
-        info!("🔄 立即执行首次日报生成...");
-        if let Err(e) = self.generate_daily_report().await {
-            warn!("首次日报生成失败: {}", e);
+        Ok(())
+    }
+    
+    /// 记录每个任务接下来的执行时间
+    fn log_schedules(&self) {
+        info!("📅 已加载 {} 个定时任务:", self.jobs.len());
+        for (_, schedule) in &self.jobs {
+            let config = &schedule.config;
+            info!(
+                "  - {}: {} ({})，最近 {} 天，{}{}",
+                config.name,
+                config.cron,
+                config.timezone,
+                config.days,
+                if config.batch { "批量模式" } else { "单条模式" },
+                if config.force { "，忽略历史记录" } else { "" }
+            );
+            for time in schedule.next_fire_times(NEXT_FIRE_TIMES) {
+                info!("      下次执行: {}", time.format("%Y-%m-%d %H:%M:%S %Z"));
+            }
         }
+    }
+    
+    /// 保持运行直到 `shutdown` 完成，然后停止调度器
+    pub async fn run_until<F>(&mut self, shutdown: F) -> Result<()>
+    where
+        F: Future<Output = ()>,
+    {
+        tokio::pin!(shutdown);
+        let mut reload = ReloadSignal::new();
+        let mut health_check = tokio::time::interval(Duration::from_secs(3600)); // 每小时检查一次状态
+        health_check.tick().await;
         
-        // 保持运行
         loop {
-            tokio::time::sleep(Duration::from_secs(3600)).await; // 每小时检查一次状态
-            
-            // 检查调度器状态
-            if let Err(e) = self.check_scheduler_health().await {
-                error!("调度器健康检查失败: {}", e);
-            }
-        }
-    }
-    
-    /// 添加日报生成任务（每 4 小时运行）
-    async fn add_daily_report_job(&mut self) -> Result<()> {
-        let reports_dir = self.reports_dir.clone();
-        
-        // 使用 cron 表达式：每 4 小时运行一次（在每天的 0, 4, 8, 12, 16, 20 点运行）
-        let job = Job::new_async("0 0 */4 * * *", move |_uuid, _l| {
-            let reports_dir = reports_dir.clone();
-            Box::pin(async move {
-                info!("⏰ 开始执行定时日报生成任务");
-                
-                match Self::execute_daily_report_task(reports_dir).await {
-                    Ok(report) => {
-                        info!("✅ 定时日报生成完成: {}", report.date.format("%Y-%m-%d %H:%M:%S"));
-                        info!("📊 生成了 {} 条新闻摘要", report.items.len());
-                    }
-                    Err(e) => {
-                        error!("❌ 定时日报生成失败: {}", e);
-                    }
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = reload.recv() => {
+                    dotenvy::dotenv_override().ok();
+                    self.reload().await;
                 }
-            })
-        })?;
+                _ = health_check.tick() => self.check_scheduler_health().await,
+            }
+        }
         
-        self.scheduler.add(job).await.map_err(|e| anyhow::anyhow!("Failed to add job: {}", e))?;
+        self.shutdown().await
+    }
+    
+    /// 添加一个定时任务
+    async fn add_job(&mut self, schedule: JobSchedule) -> Result<()> {
+        let job_config = schedule.config.clone();
+        let context = self.context.clone();
         
-        info!("📝 已添加定时任务：每 4 小时生成日报");
+        let job = Job::new_async_tz(schedule.config.cron.as_str(), schedule.timezone, move |_uuid, _l| {
+            Box::pin(Self::run_scheduled_task(job_config.clone(), context.clone()))
+        })?;
+        
+        let job_id = self.scheduler.add(job).await.map_err(|e| anyhow::anyhow!("Failed to add job: {}", e))?;
+        info!("📝 已添加定时任务 {}: {} ({})", schedule.config.name, schedule.config.cron, schedule.config.timezone);
+        self.jobs.push((job_id, schedule));
         Ok(())
     }
     
-    /// 执行日报生成任务的核心逻辑
-    async fn execute_daily_report_task(reports_dir: String) -> Result<DailyReport> {
-        // 加载配置
-        let config = Config::load()?;
+    /// 重新加载配置，新配置无效时继续使用原配置
+    ///
+    /// 只有新增、删除或设置变化的任务会被重新调度，其余任务保持不变。
+    pub async fn reload(&mut self) {
+        info!("🔄 正在重新加载配置...");
+        let (config, schedules) = match Config::reload().and_then(|config| {
+            let schedules = parse_schedules(&config)?;
+            Ok((config, schedules))
+        }) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                error!("❌ 新配置无效，继续使用原配置: {}", e);
+                return;
+            }
+        };
+        *self.context.config.write().unwrap() = config;
         
-        // 创建必要的组件
-        let rss_fetcher = RssFetcher::new();
-        let mut history_manager = HistoryManager::new()?;
+        let mut changed = false;
+        for (job_id, schedule) in std::mem::take(&mut self.jobs) {
+            if schedules.iter().any(|new| new.config == schedule.config) {
+                self.jobs.push((job_id, schedule));
+                continue;
+            }
+            if let Err(e) = self.scheduler.remove(&job_id).await {
+                error!("移除定时任务 {} 失败: {}", schedule.config.name, e);
+            }
+            info!("🗑️ 已移除定时任务 {}", schedule.config.name);
+            changed = true;
+        }
+        for schedule in schedules {
+            if self.jobs.iter().any(|(_, existing)| existing.config == schedule.config) {
+                continue;
+            }
+            let name = schedule.config.name.clone();
+            if let Err(e) = self.add_job(schedule).await {
+                error!("添加定时任务 {} 失败: {}", name, e);
+            }
+            changed = true;
+        }
         
-        // 创建 DeepSeek 客户端
-        let deepseek_client = rig::providers::deepseek::Client::from_env();
-        let agent = deepseek_client
-            .agent("deepseek-chat")
-            .preamble("你是 Rust 中文社区的专业技术编辑，负责整理每日 Rust 技术资讯。你需要按照 rustcc.cn 日报的格式和风格，用专业但易懂的中文编写技术日报。重点关注技术细节、实用价值和社区动态，保持客观中性的技术写作风格。")
-            .build();
-        
-        let daily_generator = DailyGenerator::new(agent);
-        
-        // 获取新闻
-        info!("正在获取 RSS 源...");
-        let mut all_news = Vec::new();
-        
-        for rss_url in &config.rss_sources {
-            match rss_fetcher.fetch_rss(&rss_url.url, 1).await {
-                Ok(mut items) => {
-                    info!("从 {} 获取到 {} 条新闻", rss_url.name, items.len());
-                    for item in &mut items {
-                        item.source = rss_url.name.clone();
-                    }
-                    all_news.extend(items);
+        info!("✅ 配置已重新加载");
+        if changed {
+            self.log_schedules();
+        }
+    }
+    
+    /// 按任务的 `overlap` 策略获取生成锁，应跳过本次执行时返回 `None`
+    async fn acquire_generation(job: &ScheduleConfig, shared: &SharedState) -> Result<Option<GenerationGuard>> {
+        if let Some(guard) = shared.try_lock_generation()? {
+            return Ok(Some(guard));
+        }
+        
+        match job.overlap {
+            OverlapPolicy::Skip => Ok(None),
+            OverlapPolicy::Queue => {
+                if !shared.try_enqueue() {
+                    return Ok(None);
                 }
-                Err(e) => {
-                    warn!("获取 RSS 源 {} 失败: {}", rss_url.name, e);
+                info!("⏳ 任务 {} 等待正在运行的生成任务结束", job.name);
+                let guard = shared.lock_generation().await;
+                shared.dequeue();
+                guard.map(Some)
+            }
+            OverlapPolicy::Cancel => {
+                // 只能取消本进程内的定时任务，管理任务或其他进程持有锁时跳过
+                if !shared.cancel_current_run() {
+                    return Ok(None);
                 }
+                warn!("🛑 任务 {} 已取消正在运行的定时任务", job.name);
+                shared.lock_generation().await.map(Some)
             }
         }
+    }
+    
+    /// 执行一次定时任务：获取生成锁、生成日报、记录执行结果、通知缓存失效并清理历史记录
+    async fn run_scheduled_task(job: ScheduleConfig, context: JobContext) {
+        let shared = &context.shared;
+        let mut run = TaskRun::start(&job.name);
         
-        if all_news.is_empty() {
-            return Err(anyhow::anyhow!("未获取到任何新闻"));
-        }
+        let _guard = match Self::acquire_generation(&job, shared).await {
+            Ok(Some(guard)) => guard,
+            Ok(None) => {
+                let holder = RunLock::holder().unwrap_or_default();
+                warn!("⏭️ 上一次生成仍在运行，跳过定时任务 {} {}", job.name, holder);
+                run.status = TaskRunStatus::Skipped;
+                run.error = Some("上一次生成仍在运行".to_string());
+                Self::record_run(run);
+                return;
+            }
+            Err(e) => {
+                error!("❌ 定时任务 {} 获取生成锁失败: {}", job.name, e);
+                run.error = Some(format!("获取生成锁失败: {}", e));
+                Self::record_run(run);
+                return;
+            }
+        };
+        info!("⏰ 开始执行定时任务 {}", job.name);
+        run.started_at = Utc::now();
         
-        info!("总共获取到 {} 条新闻", all_news.len());
+        // 在独立任务中执行，以便 `overlap = "cancel"` 的任务取消它
+        let config = context.config();
+        let task = tokio::spawn({
+            let job = job.clone();
+            let config = config.clone();
+            let reports_dir = context.reports_dir.clone();
+            async move { Self::execute_daily_report_task(&job, &config, reports_dir).await }
+        });
+        shared.set_current_run(Some(task.abort_handle()));
+        let result = task.await;
+        shared.set_current_run(None);
         
-        // 过滤已处理的新闻
-        let filtered_news = history_manager.filter_unprocessed(all_news);
+        let result = match result {
+            Ok(result) => result,
+            Err(e) if e.is_cancelled() => Err(anyhow::anyhow!("任务被取消")),
+            Err(e) => Err(anyhow::anyhow!("任务异常退出: {}", e)),
+        };
         
-        if filtered_news.is_empty() {
-            info!("所有新闻都已处理过，跳过本次生成");
-            // 返回一个空的日报而不是错误
-            return Ok(DailyReport {
-                date: Utc::now(),
-                items: vec![],
-                summary: "本次检查未发现新内容".to_string(),
-            });
+        run.apply_outcome(&result);
+        match &result {
+            Ok(RunOutcome::Generated { report, .. }) => {
+                shared.notify_reports_changed();
+                info!("✅ 定时任务 {} 完成: {}", job.name, report.id);
+                info!("📊 生成了 {} 条新闻摘要", report.items.len());
+            }
+            Ok(RunOutcome::NothingNew { .. }) => {
+                info!("✅ 定时任务 {} 完成: 本次检查未发现新内容", job.name);
+            }
+            Ok(RunOutcome::NoNews) | Err(_) => {
+                error!("❌ 定时任务 {} 失败: {}", job.name, run.error.as_deref().unwrap_or_default());
+            }
         }
         
-        // 生成日报
-        info!("正在生成日报摘要，处理 {} 条新新闻...", filtered_news.len());
-        let daily_report = daily_generator.generate_daily_report(filtered_news.clone(), true).await?;
-        
-        // 标记新闻为已处理
-        history_manager.mark_as_processed(&filtered_news)?;
-        
-        // 保存报告到 JSON 文件
-        std::fs::create_dir_all(&reports_dir)?;
-        let report_filename = format!("{}/{}.json", reports_dir, daily_report.date.format("%Y-%m-%d-%H%M"));
-        let report_json = serde_json::to_string_pretty(&daily_report)?;
-        std::fs::write(&report_filename, report_json)?;
-        info!("报告已保存到: {}", report_filename);
+        Self::record_run(run);
+        Self::apply_history_retention(&config);
+    }
+    
+    /// 记录监控指标并写入任务执行记录
+    fn record_run(run: TaskRun) {
+        telemetry::record_scheduled_run(&run.job, run.status.as_str());
+        run.save();
+    }
+    
+    /// 执行日报生成任务的核心逻辑
+    async fn execute_daily_report_task(job: &ScheduleConfig, config: &Config, reports_dir: String) -> Result<RunOutcome> {
+        let mut history_manager = HistoryManager::new()?;
+        let report_store = ReportStore::new(&reports_dir);
+        // 被取消的任务可能遗留待提交的记录
+        history_manager.recover_pending_runs(&report_store)?;
+        let options = RunOptions {
+            force: job.force,
+            batch: job.batch,
+            days: job.days,
+        };
         
-        Ok(daily_report)
+        let outcome = pipeline::run(config, &options, &report_store, &mut history_manager).await?;
+        if let RunOutcome::Generated { report, .. } = &outcome {
+            if let Some(markdown_dir) = &job.markdown_dir {
+                std::fs::create_dir_all(markdown_dir)?;
+                let path = std::path::Path::new(markdown_dir).join(format!("{}.md", report.id));
+                std::fs::write(&path, format_daily_report(report))?;
+                info!("Markdown 日报已保存到: {}", path.display());
+            }
+        }
+        Ok(outcome)
     }
     
-    /// 手动触发日报生成
-    pub async fn generate_daily_report(&self) -> Result<DailyReport> {
-        Self::execute_daily_report_task(self.reports_dir.clone()).await
+    /// 按配置的保留策略清理历史记录，失败时只记录警告
+    fn apply_history_retention(config: &Config) {
+        let result = HistoryManager::new().and_then(|mut history_manager| {
+            history_manager.apply_retention(&config.history)
+        });
+        
+        if let Err(e) = result {
+            warn!("自动清理历史记录失败: {}", e);
+        }
     }
     
-    /// 检查调度器健康状态
-    async fn check_scheduler_health(&self) -> Result<()> {
-        // 这里可以添加健康检查逻辑
-        // 比如检查磁盘空间、网络连接等
-        Ok(())
+    /// 检查调度器健康状态，未通过的检查项记录到日志
+    async fn check_scheduler_health(&self) {
+        let store = ReportStore::new(&self.context.reports_dir);
+        let options = health::CheckOptions { probe_llm: true, schedules: true };
+        let report = health::run_checks(&self.context.config(), &store, options).await;
+        for check in report.problems() {
+            if check.status == CheckStatus::Fail {
+                error!("健康检查 {} 失败: {}", check.name, check.message);
+            } else {
+                warn!("健康检查 {} 警告: {}", check.name, check.message);
+            }
+        }
     }
     
-    /// 优雅停止调度器
+    /// 优雅停止调度器，等待进行中的生成任务完成
     pub async fn shutdown(&mut self) -> Result<()> {
         info!("🛑 正在停止定时任务调度器...");
         self.scheduler.shutdown().await.map_err(|e| anyhow::anyhow!("Failed to shutdown scheduler: {}", e))?;
+        
+        // 超时退出也不会丢失数据，未完成的任务在下次启动时恢复
+        if tokio::time::timeout(SHUTDOWN_GRACE, self.context.shared.wait_idle()).await.is_err() {
+            warn!("等待进行中的日报生成超时，未完成的任务将在下次启动时恢复");
+        }
         info!("✅ 定时任务调度器已停止");
         Ok(())
     }
     
     /// 获取下次执行时间
+    ///
+    /// 调度器已启动时取各任务的下次触发时间；未启动时（如 `--task-stats`）按配置计算。
     pub async fn next_execution_time(&self) -> Option<DateTime<Utc>> {
-        // 这里可以实现获取下次执行时间的逻辑
-        // tokio-cron-scheduler 可能需要额外的方法来实现这个功能
-        None
-    }
-    
-    /// 获取任务执行统计
-    pub async fn get_task_stats(&self) -> TaskStats {
-        TaskStats {
-            total_executions: 0, // 需要实现统计逻辑
-            successful_executions: 0,
-            failed_executions: 0,
-            last_execution: None,
-            next_execution: self.next_execution_time().await,
+        if !self.jobs.is_empty() {
+            let mut scheduler = self.scheduler.clone();
+            let mut next: Option<DateTime<Utc>> = None;
+            for (job_id, _) in &self.jobs {
+                match scheduler.next_tick_for_job(*job_id).await {
+                    Ok(Some(tick)) => next = Some(next.map_or(tick, |next| next.min(tick))),
+                    Ok(None) => {}
+                    Err(e) => warn!("获取任务 {} 的下次执行时间失败: {}", job_id, e),
+                }
+            }
+            return next;
         }
+        
+        let schedules = Config::load()
+            .and_then(|config| parse_schedules(&config))
+            .map_err(|e| warn!("无法读取定时任务配置: {}", e))
+            .ok()?;
+        schedules
+            .iter()
+            .filter_map(|schedule| schedule.next_fire_times(1).into_iter().next())
+            .map(|time| time.with_timezone(&Utc))
+            .min()
+    }
+    
+    /// 根据持久化的执行记录统计任务执行情况
+    pub async fn get_task_stats(&self) -> Result<TaskStats> {
+        let runs = RunLog::open().runs()?;
+        let count = |status: TaskRunStatus| runs.iter().filter(|run| run.status == status).count() as u64;
+        
+        Ok(TaskStats {
+            total_executions: runs.len() as u64,
+            successful_executions: count(TaskRunStatus::Succeeded),
+            skipped_executions: count(TaskRunStatus::Skipped),
+            failed_executions: count(TaskRunStatus::Failed),
+            last_execution: runs.last().map(|run| run.started_at),
+            next_execution: self.next_execution_time().await,
+            recent_runs: runs.iter().rev().take(RECENT_RUNS).cloned().collect(),
+        })
     }
 }
 
@@ -196,18 +537,35 @@ impl TaskScheduler {
 pub struct TaskStats {
     pub total_executions: u64,
     pub successful_executions: u64,
+    /// 没有新内容而跳过生成的次数
+    pub skipped_executions: u64,
     pub failed_executions: u64,
     pub last_execution: Option<DateTime<Utc>>,
     pub next_execution: Option<DateTime<Utc>>,
+    /// 最近的执行记录，从新到旧
+    pub recent_runs: Vec<TaskRun>,
 }
 
 impl TaskStats {
+    /// 成功率，跳过的执行也算作成功；没有执行记录时为 None
+    pub fn success_rate(&self) -> Option<f64> {
+        if self.total_executions == 0 {
+            return None;
+        }
+        Some((self.total_executions - self.failed_executions) as f64 / self.total_executions as f64 * 100.0)
+    }
+    
     pub fn display(&self) {
         println!("📊 定时任务统计:");
         println!("  总执行次数: {}", self.total_executions);
         println!("  成功次数: {}", self.successful_executions);
+        println!("  跳过次数: {}", self.skipped_executions);
         println!("  失败次数: {}", self.failed_executions);
         
+        if let Some(rate) = self.success_rate() {
+            println!("  成功率: {:.1}%", rate);
+        }
+        
         if let Some(last) = &self.last_execution {
             println!("  上次执行: {}", last.format("%Y-%m-%d %H:%M:%S UTC"));
         }
@@ -215,5 +573,32 @@ impl TaskStats {
         if let Some(next) = &self.next_execution {
             println!("  下次执行: {}", next.format("%Y-%m-%d %H:%M:%S UTC"));
         }
+        
+        if !self.recent_runs.is_empty() {
+            println!("\n📋 最近执行记录:");
+            for run in &self.recent_runs {
+                let status = match run.status {
+                    TaskRunStatus::Succeeded => "✅",
+                    TaskRunStatus::Skipped => "⏭️",
+                    TaskRunStatus::Failed => "❌",
+                };
+                println!(
+                    "  {} {} [{}] 耗时 {}s，获取 {} 条，新增 {} 条，摘要 {} 条",
+                    status,
+                    run.started_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                    run.job,
+                    run.duration_secs(),
+                    run.fetched,
+                    run.filtered,
+                    run.summarized
+                );
+                if let Some(report_id) = &run.report_id {
+                    println!("      日报: {}", report_id);
+                }
+                if let Some(error) = &run.error {
+                    println!("      错误: {}", error);
+                }
+            }
+        }
     }
 }
\ No newline at end of file
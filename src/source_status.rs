@@ -0,0 +1,264 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::{Config, RssSource, FetchConfig};
+use crate::report_store::write_atomic;
+use crate::rss_parser::{CacheValidators, FetchedFeed};
+
+/// 单个 RSS 源的获取状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceStatus {
+    /// 记录状态时源的 URL，URL 变化后重新开始统计
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub last_attempt: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_success: Option<DateTime<Utc>>,
+    /// 连续失败次数，成功一次后清零
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// 见过的最新一条条目的发布时间，统计 feed 中的全部条目，不限于 Rust 相关内容
+    #[serde(default)]
+    pub last_item_at: Option<DateTime<Utc>>,
+    /// 开始统计以来见过的新条目数（同样不经过过滤），用于计算平均每天的条目数
+    #[serde(default)]
+    pub items_seen: u64,
+    #[serde(default)]
+    pub tracking_since: Option<DateTime<Utc>>,
+    /// 因连续失败被暂停获取的时间
+    #[serde(default)]
+    pub quarantined_at: Option<DateTime<Utc>>,
+    /// 上次成功响应的 `ETag`，下次获取时作为 `If-None-Match` 发送
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// 上次成功响应的 `Last-Modified`，下次获取时作为 `If-Modified-Since` 发送
+    #[serde(default)]
+    pub last_modified: Option<String>,
+}
+
+impl SourceStatus {
+    /// 开始统计以来平均每天的新新闻数，统计不足一天时返回 `None`
+    pub fn items_per_day(&self, now: DateTime<Utc>) -> Option<f64> {
+        let days = (now - self.tracking_since?).num_seconds() as f64 / 86400.0;
+        (days >= 1.0).then(|| self.items_seen as f64 / days)
+    }
+
+    /// 超过 `stale_days` 天没有新内容
+    pub fn is_stale(&self, policy: &FetchConfig, now: DateTime<Utc>) -> bool {
+        if policy.stale_days == 0 {
+            return false;
+        }
+        let threshold = now - Duration::days(policy.stale_days as i64);
+        match self.last_item_at.or(self.tracking_since) {
+            Some(time) => time < threshold,
+            None => false,
+        }
+    }
+
+    /// 暂停获取后下一次重试的时间
+    pub fn next_retry(&self, policy: &FetchConfig) -> Option<DateTime<Utc>> {
+        self.quarantined_at?;
+        let last_attempt = self.last_attempt?;
+        Some(last_attempt + Duration::hours(policy.retry_after_hours as i64))
+    }
+}
+
+/// 所有 RSS 源的获取状态，保存在 `~/.rust-daily/source_status.json`
+pub struct SourceStatusStore {
+    path: PathBuf,
+    sources: BTreeMap<String, SourceStatus>,
+}
+
+impl SourceStatusStore {
+    pub fn load() -> Result<Self> {
+        let path = dirs::home_dir()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
+            .join(".rust-daily")
+            .join("source_status.json");
+        let sources = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, sources })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SourceStatus> {
+        self.sources.get(name)
+    }
+
+    /// 该源当前的状态，URL 已变化时视为新源
+    pub fn current(&self, source: &RssSource) -> Option<&SourceStatus> {
+        self.get(&source.name)
+            .filter(|status| status.url.is_empty() || status.url == source.url)
+    }
+
+    /// 本次是否应该获取该源：未被暂停，或距上次尝试已超过重试间隔
+    pub fn should_fetch(&self, source: &RssSource, policy: &FetchConfig, now: DateTime<Utc>) -> bool {
+        match self.current(source).and_then(|status| status.next_retry(policy)) {
+            Some(next_retry) => now >= next_retry,
+            None => true,
+        }
+    }
+
+    /// 条件请求使用的验证信息，没有记录或 URL 已变化时为空
+    pub fn validators(&self, source: &RssSource) -> CacheValidators {
+        self.current(source)
+            .map(|status| CacheValidators {
+                etag: status.etag.clone(),
+                last_modified: status.last_modified.clone(),
+            })
+            .unwrap_or_default()
+    }
+
+    fn entry(&mut self, source: &RssSource) -> &mut SourceStatus {
+        let status = self.sources.entry(source.name.clone()).or_default();
+        if !status.url.is_empty() && status.url != source.url {
+            *status = SourceStatus::default();
+        }
+        status.url = source.url.clone();
+        status.tracking_since.get_or_insert_with(Utc::now);
+        status
+    }
+
+    /// 记录一次成功获取（包括 304），返回该源是否因此解除暂停
+    ///
+    /// 新内容按 feed 中全部条目的发布时间统计，不受时间窗口和相关性过滤的影响。
+    pub fn record_success(&mut self, source: &RssSource, feed: &FetchedFeed) -> bool {
+        let now = Utc::now();
+        let status = self.entry(source);
+        status.last_attempt = Some(now);
+        status.last_success = Some(now);
+        status.consecutive_failures = 0;
+        status.last_error = None;
+        status.etag = feed.validators.etag.clone();
+        status.last_modified = feed.validators.last_modified.clone();
+
+        let previous = status.last_item_at;
+        let new_items = feed
+            .published
+            .iter()
+            .copied()
+            .filter(|date| previous.is_none_or(|previous| *date > previous));
+        for date in new_items {
+            status.items_seen += 1;
+            status.last_item_at = Some(status.last_item_at.map_or(date, |last| last.max(date)));
+        }
+
+        status.quarantined_at.take().is_some()
+    }
+
+    /// 记录一次获取失败，返回该源是否因此被暂停获取
+    pub fn record_failure(&mut self, source: &RssSource, error: &str, policy: &FetchConfig) -> bool {
+        let now = Utc::now();
+        let status = self.entry(source);
+        status.last_attempt = Some(now);
+        status.consecutive_failures += 1;
+        status.last_error = Some(error.to_string());
+
+        let quarantine = policy.quarantine_after > 0
+            && status.consecutive_failures >= policy.quarantine_after
+            && status.quarantined_at.is_none();
+        if quarantine {
+            status.quarantined_at = Some(now);
+        }
+        quarantine
+    }
+
+    /// 解除暂停并清零失败次数，下次生成日报时立即获取该源，`--resume-source` 使用
+    ///
+    /// 返回该源之前是否处于暂停或失败状态。
+    pub fn resume(&mut self, name: &str) -> bool {
+        let Some(status) = self.sources.get_mut(name) else {
+            return false;
+        };
+        let was_failing = status.quarantined_at.is_some() || status.consecutive_failures > 0;
+        status.quarantined_at = None;
+        status.consecutive_failures = 0;
+        was_failing
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        write_atomic(&self.path, serde_json::to_string_pretty(&self.sources)?.as_bytes())
+    }
+
+    /// 显示所有已配置源的健康状况，`--source-report` 使用
+    pub fn display_report(&self, config: &Config) {
+        let policy = &config.fetch;
+        let now = Utc::now();
+        let format_time = |time: Option<DateTime<Utc>>| {
+            time.map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "-".to_string())
+        };
+
+        println!("📡 RSS 源健康报告:");
+        let (mut healthy, mut failing, mut quarantined, mut stale, mut unknown) = (0, 0, 0, 0, 0);
+        for source in &config.sources {
+            if source.name.trim().is_empty() || source.url.trim().is_empty() {
+                println!("  ❌ (名称或 URL 为空) — 配置无效，已跳过");
+                failing += 1;
+                continue;
+            }
+
+            let Some(status) = self.current(source) else {
+                println!("  ❔ {} — 尚未获取", source.name);
+                println!("      {}", source.url);
+                unknown += 1;
+                continue;
+            };
+
+            if status.quarantined_at.is_some() {
+                quarantined += 1;
+                println!(
+                    "  ⏸️ {} — 连续失败 {} 次，已于 {} 暂停获取，下次重试 {}",
+                    source.name,
+                    status.consecutive_failures,
+                    format_time(status.quarantined_at),
+                    format_time(status.next_retry(policy))
+                );
+            } else if status.consecutive_failures > 0 {
+                failing += 1;
+                println!("  ❌ {} — 连续失败 {} 次", source.name, status.consecutive_failures);
+            } else if status.is_stale(policy, now) {
+                stale += 1;
+                println!("  💤 {} — 超过 {} 天没有新内容", source.name, policy.stale_days);
+            } else {
+                healthy += 1;
+                println!("  ✅ {}", source.name);
+            }
+
+            println!("      {}", source.url);
+            println!(
+                "      上次成功: {} | 最新内容: {} | 平均每天: {}",
+                format_time(status.last_success),
+                format_time(status.last_item_at),
+                status
+                    .items_per_day(now)
+                    .map(|rate| format!("{:.1} 条", rate))
+                    .unwrap_or_else(|| "-".to_string())
+            );
+            if let Some(error) = status.last_error.as_deref().filter(|_| status.consecutive_failures > 0) {
+                println!("      最近错误: {}", error);
+            }
+        }
+
+        println!();
+        println!(
+            "✅ 正常 {} | ❌ 失败 {} | ⏸️ 已暂停 {} | 💤 陈旧 {} | ❔ 未获取 {}",
+            healthy, failing, quarantined, stale, unknown
+        );
+        if quarantined > 0 {
+            println!("💡 已暂停的源到重试时间后自动重试，成功即恢复；修复后可用 --resume-source \"名称\" 立即恢复");
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::OwnedMutexGuard;
+use tokio::task::AbortHandle;
+
+use crate::run_lock::RunLock;
+
+/// 等待其他进程释放生成锁时的轮询间隔
+const LOCK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 同一进程内调度器、RSS 服务器和管理 API 共享的状态
+#[derive(Debug, Clone, Default)]
+pub struct SharedState {
+    /// 同一时间只允许一个任务修改日报和历史记录
+    generation: Arc<tokio::sync::Mutex<()>>,
+    /// 是否已有定时任务在排队等待生成锁
+    queued: Arc<AtomicBool>,
+    /// 正在运行的定时任务，可被 `overlap = "cancel"` 的任务取消
+    current_run: Arc<Mutex<Option<AbortHandle>>>,
+    /// 日报每次变化时递增，RSS 服务器据此清空缓存
+    revision: Arc<AtomicU64>,
+}
+
+/// 生成锁，同时持有进程内的互斥锁和跨进程的锁文件，离开作用域时释放
+#[derive(Debug)]
+pub struct GenerationGuard {
+    _file: RunLock,
+    _local: OwnedMutexGuard<()>,
+}
+
+impl SharedState {
+    /// 等待其他任务（包括其他进程）结束后获取生成锁
+    pub async fn lock_generation(&self) -> Result<GenerationGuard> {
+        let local = self.generation.clone().lock_owned().await;
+        loop {
+            if let Some(file) = RunLock::try_acquire()? {
+                return Ok(GenerationGuard { _file: file, _local: local });
+            }
+            tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+        }
+    }
+
+    /// 尝试获取生成锁，已有任务在运行时返回 `None`
+    pub fn try_lock_generation(&self) -> Result<Option<GenerationGuard>> {
+        let Ok(local) = self.generation.clone().try_lock_owned() else {
+            return Ok(None);
+        };
+        Ok(RunLock::try_acquire()?.map(|file| GenerationGuard { _file: file, _local: local }))
+    }
+
+    /// 等待本进程内进行中的生成任务结束
+    pub async fn wait_idle(&self) {
+        let _ = self.generation.lock().await;
+    }
+
+    /// 标记有任务排队，已有任务排队时返回 false
+    pub fn try_enqueue(&self) -> bool {
+        !self.queued.swap(true, Ordering::SeqCst)
+    }
+
+    pub fn dequeue(&self) {
+        self.queued.store(false, Ordering::SeqCst);
+    }
+
+    /// 登记正在运行的定时任务
+    pub fn set_current_run(&self, handle: Option<AbortHandle>) {
+        *self.current_run.lock().unwrap() = handle;
+    }
+
+    /// 取消正在运行的定时任务，没有可取消的任务时返回 false
+    pub fn cancel_current_run(&self) -> bool {
+        match self.current_run.lock().unwrap().take() {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 通知日报已变化（生成、重新生成或删除）
+    pub fn notify_reports_changed(&self) {
+        self.revision.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::SeqCst)
+    }
+}
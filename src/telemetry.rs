@@ -0,0 +1,153 @@
+use anyhow::Result;
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use metrics::{counter, describe_counter, describe_histogram, histogram, Unit};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+/// 耗时类直方图的分桶（秒），覆盖从快速 HTTP 请求到较慢的日报生成
+const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0,
+];
+/// 清理直方图缓冲区的间隔，没有抓取时也不会无限增长
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// 安装 Prometheus 指标记录器，在 RSS 服务器和守护进程启动时调用
+///
+/// 未安装时所有记录函数都是空操作。
+pub fn install() {
+    if HANDLE.get().is_some() {
+        return;
+    }
+
+    let handle = match PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), DURATION_BUCKETS)
+        .and_then(|builder| builder.install_recorder())
+    {
+        Ok(handle) => handle,
+        Err(e) => {
+            warn!("安装 Prometheus 指标记录器失败: {}", e);
+            return;
+        }
+    };
+    describe();
+
+    let upkeep = handle.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(UPKEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            upkeep.run_upkeep();
+        }
+    });
+    let _ = HANDLE.set(handle);
+}
+
+/// Prometheus 文本格式的指标，未安装记录器时返回 `None`
+pub fn render() -> Option<String> {
+    HANDLE.get().map(|handle| handle.render())
+}
+
+/// `GET /metrics`，RSS 服务器和守护进程的指标端口共用
+pub async fn metrics_handler() -> Response {
+    match render() {
+        Some(body) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+            body,
+        ).into_response(),
+        None => (StatusCode::NOT_FOUND, "指标未启用").into_response(),
+    }
+}
+
+/// 单独运行守护进程时在 `addr` 上提供 `/metrics`，绑定成功后在后台运行
+pub async fn serve(addr: SocketAddr) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    info!("📊 Prometheus 指标: http://{}/metrics", listener.local_addr()?);
+    
+    let app = Router::new().route("/metrics", get(metrics_handler));
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            warn!("指标服务异常退出: {}", e);
+        }
+    });
+    Ok(())
+}
+
+fn describe() {
+    describe_counter!("rust_daily_feed_fetches_total", "RSS 源获取次数，按来源和结果（success/failure/not_modified）");
+    describe_histogram!("rust_daily_feed_fetch_duration_seconds", Unit::Seconds, "RSS 源获取耗时");
+    describe_counter!("rust_daily_items_fetched_total", "从 RSS 源获取到的新闻数");
+    describe_counter!("rust_daily_items_duplicate_total", "因已处理过而被过滤的新闻数");
+    describe_counter!("rust_daily_items_new_total", "去重后交给 LLM 处理的新闻数");
+    describe_counter!("rust_daily_llm_requests_total", "LLM 请求次数，按用途和结果");
+    describe_histogram!("rust_daily_llm_request_duration_seconds", Unit::Seconds, "LLM 请求耗时");
+    describe_counter!("rust_daily_llm_tokens_total", "LLM 消耗的 token 数，按类型（input/output）");
+    describe_counter!("rust_daily_report_generations_total", "日报生成任务次数，按结果");
+    describe_histogram!("rust_daily_report_generation_duration_seconds", Unit::Seconds, "日报生成任务耗时");
+    describe_counter!("rust_daily_scheduled_runs_total", "定时任务执行次数，按任务和结果");
+    describe_counter!("rust_daily_http_requests_total", "HTTP 请求数，按方法、路由和状态码");
+    describe_histogram!("rust_daily_http_request_duration_seconds", Unit::Seconds, "HTTP 请求耗时");
+}
+
+/// 一次 RSS 源获取，`outcome` 为 success、failure 或 not_modified
+pub fn record_feed_fetch(source: &str, outcome: &'static str, elapsed: Duration) {
+    counter!("rust_daily_feed_fetches_total", "source" => source.to_string(), "outcome" => outcome).increment(1);
+    histogram!("rust_daily_feed_fetch_duration_seconds", "source" => source.to_string()).record(elapsed.as_secs_f64());
+}
+
+/// 一次生成任务中获取到的新闻数和去重后剩余的新闻数
+pub fn record_items(fetched: usize, new: usize) {
+    counter!("rust_daily_items_fetched_total").increment(fetched as u64);
+    counter!("rust_daily_items_duplicate_total").increment(fetched.saturating_sub(new) as u64);
+    counter!("rust_daily_items_new_total").increment(new as u64);
+}
+
+/// 一次 LLM 请求，成功时记录 token 用量
+pub fn record_llm_request(kind: &'static str, elapsed: Duration, tokens: Option<(u64, u64)>) {
+    let outcome = if tokens.is_some() { "success" } else { "error" };
+    counter!("rust_daily_llm_requests_total", "kind" => kind, "outcome" => outcome).increment(1);
+    histogram!("rust_daily_llm_request_duration_seconds", "kind" => kind).record(elapsed.as_secs_f64());
+    if let Some((input, output)) = tokens {
+        counter!("rust_daily_llm_tokens_total", "type" => "input").increment(input);
+        counter!("rust_daily_llm_tokens_total", "type" => "output").increment(output);
+    }
+}
+
+/// 一次日报生成任务，`outcome` 为 generated、nothing_new、no_news 或 error
+pub fn record_generation(outcome: &'static str, elapsed: Duration) {
+    counter!("rust_daily_report_generations_total", "outcome" => outcome).increment(1);
+    histogram!("rust_daily_report_generation_duration_seconds", "outcome" => outcome).record(elapsed.as_secs_f64());
+}
+
+/// 一次定时任务执行
+pub fn record_scheduled_run(job: &str, status: &'static str) {
+    counter!("rust_daily_scheduled_runs_total", "job" => job.to_string(), "status" => status).increment(1);
+}
+
+/// 一次 HTTP 请求，`route` 为匹配到的路由模板，避免按具体路径产生过多序列
+pub fn record_http_request(method: &str, route: &str, status: u16, elapsed: Duration) {
+    counter!(
+        "rust_daily_http_requests_total",
+        "method" => method.to_string(),
+        "route" => route.to_string(),
+        "status" => status.to_string()
+    )
+    .increment(1);
+    histogram!(
+        "rust_daily_http_request_duration_seconds",
+        "method" => method.to_string(),
+        "route" => route.to_string()
+    )
+    .record(elapsed.as_secs_f64());
+}